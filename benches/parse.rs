@@ -0,0 +1,68 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rss::Channel;
+use scraper::{Html, Selector};
+use upwork_tui::core_opt::parse_xml::{parse_xml, JobStream};
+
+/// How many copies of `job.xml` make up the archive benchmark.
+const ARCHIVE_FEEDS: usize = 50;
+
+/// The previous approach: the whole channel in memory, then a DOM per item
+/// just to find its `<b>` keys.
+fn parse_dom(feed: &[u8]) -> Vec<HashMap<String, String>> {
+    let channel = Channel::read_from(feed).unwrap();
+    let selector = Selector::parse("b").unwrap();
+
+    channel
+        .items
+        .iter()
+        .filter_map(|item| item.description.as_deref())
+        .map(|description| {
+            let doc = Html::parse_document(description);
+            doc.select(&selector)
+                .filter_map(|element| {
+                    let value = element.next_sibling()?.value().as_text()?;
+                    let value: Vec<&str> =
+                        value.trim_start_matches(':').split_whitespace().collect();
+                    Some((element.inner_html(), value.join(" ")))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let feed = fs::read("job.xml").unwrap();
+    let archive = vec![&feed[..]; ARCHIVE_FEEDS].join(&b'\n');
+
+    let mut group = c.benchmark_group("job.xml");
+    group.bench_function("dom", |b| b.iter(|| parse_dom(black_box(&feed))));
+    group.bench_function("stream", |b| {
+        b.iter(|| parse_xml(black_box(&feed[..])).unwrap())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("archive");
+    group.sample_size(20);
+    group.bench_function("dom", |b| {
+        b.iter(|| {
+            // The DOM path cannot read concatenated feeds, so it parses each copy.
+            for _ in 0..ARCHIVE_FEEDS {
+                black_box(parse_dom(black_box(&feed)));
+            }
+        })
+    });
+    group.bench_function("stream", |b| {
+        b.iter(|| {
+            JobStream::new(black_box(&archive[..]))
+                .collect::<eyre::Result<Vec<_>>>()
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,822 @@
+mod error_log;
+mod ui;
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use color_eyre::eyre::{self, eyre};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+use tokio::sync::mpsc;
+use tracing::Level;
+
+use crate::{
+    cli::Cli,
+    config::{self, Config},
+    dedup::Duplicates,
+    export::{export, ExportFormat},
+    fetch::{QueryStatus, Scheduler},
+    history::{History, HistoryQuery},
+    keymap::{Action, Keymap},
+    logging::LogBuffer,
+    pipeline::{Pipeline, Stage},
+    proposal,
+    scoring::Scorer,
+    store::{JobStore, StoredJob, View},
+    theme::Theme,
+    tui::{Event, Tui},
+    webhook::Webhook,
+    JobPost,
+};
+use error_log::ErrorLog;
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+const PAGE_SIZE: isize = 10;
+const HISTORY_LIMIT: usize = 500;
+
+pub enum Message {
+    Fetched {
+        query: String,
+        result: eyre::Result<Vec<JobPost>>,
+    },
+    WebhookFailed(eyre::Report),
+    ConfigReloaded(eyre::Result<Box<Config>>),
+}
+
+pub struct App {
+    config: Config,
+    config_path: PathBuf,
+    cli: Cli,
+    store: JobStore,
+    store_path: PathBuf,
+    history: History,
+    history_mode: bool,
+    history_jobs: Vec<StoredJob>,
+    show_expired: bool,
+    rank: bool,
+    view: View,
+    webhook: Option<Arc<Webhook>>,
+    keymap: Keymap,
+    theme: Theme,
+    scorer: Scorer,
+    duplicates: Duplicates,
+    list_state: ListState,
+    board: bool,
+    board_stage: Stage,
+    board_state: ListState,
+    scheduler: Scheduler,
+    fetches: BTreeMap<String, QueryStatus>,
+    next_poll: Instant,
+    spinner: usize,
+    new_jobs: usize,
+    errors: ErrorLog,
+    show_errors: bool,
+    logs: LogBuffer,
+    show_logs: bool,
+    log_level: Level,
+    /// Scroll offset of the error or log panel, whichever is open
+    panel_scroll: u16,
+    export_prompt: bool,
+    search: String,
+    search_input: bool,
+    note_input: Option<String>,
+    pending_draft: Option<String>,
+    show_help: bool,
+    status: String,
+    should_quit: bool,
+}
+
+impl App {
+    pub fn new(
+        config: Config,
+        config_path: PathBuf,
+        cli: Cli,
+        logs: LogBuffer,
+    ) -> eyre::Result<Self> {
+        let store_path = JobStore::default_path()?;
+        let mut store = JobStore::load(&store_path)?;
+        store.prune(&config.retention, Utc::now().timestamp());
+
+        let mut history = History::open(&History::default_path()?)?;
+        history.record(store.iter().map(|stored| (&stored.post, stored.first_seen)))?;
+
+        let webhook = build_webhook(&config)?;
+        let keymap = Keymap::from_config(&config.keybindings)?;
+        let theme = Theme::from_config(&config.theme)?;
+        let scorer = Scorer::from_config(&config);
+        let duplicates = detect_duplicates(&store, &config);
+        let scheduler = Scheduler::new(&config.fetch);
+
+        Ok(Self {
+            show_expired: cli.show_expired,
+            rank: config.scoring.rank,
+            view: View::default(),
+            config,
+            config_path,
+            cli,
+            store,
+            store_path,
+            history,
+            history_mode: false,
+            history_jobs: Vec::new(),
+            webhook,
+            keymap,
+            theme,
+            scorer,
+            duplicates,
+            list_state: ListState::default().with_selected(Some(0)),
+            board: false,
+            board_stage: Stage::New,
+            board_state: ListState::default().with_selected(Some(0)),
+            scheduler,
+            fetches: BTreeMap::new(),
+            next_poll: Instant::now(),
+            spinner: 0,
+            new_jobs: 0,
+            errors: ErrorLog::default(),
+            show_errors: false,
+            logs,
+            show_logs: false,
+            log_level: Level::INFO,
+            panel_scroll: 0,
+            export_prompt: false,
+            search: String::new(),
+            search_input: false,
+            note_input: None,
+            pending_draft: None,
+            show_help: false,
+            status: String::new(),
+            should_quit: false,
+        })
+    }
+
+    pub async fn run(&mut self) -> eyre::Result<()> {
+        let (message_tx, mut message_rx) = mpsc::unbounded_channel();
+        let mut poll = tokio::time::interval(self.config.poll_interval);
+
+        let reload_tx = message_tx.clone();
+        let _watcher = config::watch(&self.config_path, move |config| {
+            let _ = reload_tx.send(Message::ConfigReloaded(config.map(Box::new)));
+        })?;
+
+        let mut tui = Tui::new(TICK_RATE)?;
+        tui.enter()?;
+
+        loop {
+            tokio::select! {
+                event = tui.next() => self.handle_event(event?, &message_tx),
+                Some(message) = message_rx.recv() => self.handle_message(message, &message_tx),
+                _ = poll.tick() => {
+                    self.next_poll = Instant::now() + self.config.poll_interval;
+                    self.refresh(&message_tx);
+                }
+            }
+
+            if self.should_quit {
+                break;
+            }
+
+            if let Some(id) = self.pending_draft.take() {
+                tui.exit()?;
+                self.edit_draft(&id);
+                tui.enter()?;
+            }
+
+            if poll.period() != self.config.poll_interval {
+                poll = tokio::time::interval(self.config.poll_interval);
+            }
+
+            tui.terminal.draw(|frame| ui::render(frame, self))?;
+        }
+
+        tui.exit()?;
+        self.store.save(&self.store_path)?;
+
+        Ok(())
+    }
+
+    fn visible_jobs(&self) -> Vec<&StoredJob> {
+        let search = self.search.to_lowercase();
+        let now = Utc::now().timestamp();
+
+        let jobs = self
+            .store
+            .visible(self.view, &self.config.filters, self.show_expired, now)
+            .into_iter()
+            .filter(|stored| search.is_empty() || matches_search(&stored.post, &search))
+            .collect::<Vec<_>>();
+
+        // Drop reposts whose original is listed too, so each post shows once.
+        let jobs = if self.config.dedup.collapse {
+            let listed: HashSet<&str> = jobs.iter().map(|stored| stored.post.id()).collect();
+            jobs.into_iter()
+                .filter(|stored| {
+                    self.duplicates
+                        .original_of(stored.post.id())
+                        .is_none_or(|original| !listed.contains(original))
+                })
+                .collect()
+        } else {
+            jobs
+        };
+
+        if !self.rank {
+            return jobs;
+        }
+
+        let mut scored: Vec<(f64, &StoredJob)> = jobs
+            .into_iter()
+            .map(|stored| (self.scorer.score(&stored.post, now).total, stored))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        scored.into_iter().map(|(_, stored)| stored).collect()
+    }
+
+    fn selected_id(&self) -> Option<String> {
+        self.selected_job()
+            .map(|stored| stored.post.id().to_owned())
+    }
+
+    /// The list the cursor moves in: the focused board column, the history
+    /// search results or the job list.
+    fn current_jobs(&self) -> Vec<&StoredJob> {
+        if self.board {
+            self.store.in_stage(self.board_stage)
+        } else if self.history_mode {
+            // Prefer the store's copy so annotations show on jobs still in it.
+            self.history_jobs
+                .iter()
+                .map(|found| self.store.get(found.post.id()).unwrap_or(found))
+                .collect()
+        } else {
+            self.visible_jobs()
+        }
+    }
+
+    fn search_history(&mut self) {
+        let query = HistoryQuery {
+            text: self.search.clone(),
+            skill: None,
+            limit: HISTORY_LIMIT,
+        };
+
+        match self.history.search(&query) {
+            Ok(hits) => {
+                self.history_jobs = hits
+                    .into_iter()
+                    .map(|hit| StoredJob::new(hit.post, hit.first_seen))
+                    .collect();
+            }
+            Err(err) => self.report_error("searching history", err),
+        }
+    }
+
+    fn current_state(&mut self) -> &mut ListState {
+        if self.board {
+            &mut self.board_state
+        } else {
+            &mut self.list_state
+        }
+    }
+
+    fn selected_job(&self) -> Option<&StoredJob> {
+        let state = if self.board {
+            &self.board_state
+        } else {
+            &self.list_state
+        };
+        let index = state.selected()?;
+        self.current_jobs().get(index).copied()
+    }
+
+    fn handle_event(&mut self, event: Event, message_tx: &mpsc::UnboundedSender<Message>) {
+        match event {
+            Event::Key(key) => self.handle_key(key, message_tx),
+            Event::Error(err) => self.report_error("terminal", eyre!(err)),
+            Event::Tick if self.in_flight() => self.spinner = self.spinner.wrapping_add(1),
+            Event::Tick | Event::Resize(_, _) => (),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, message_tx: &mpsc::UnboundedSender<Message>) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.should_quit = true;
+            return;
+        }
+
+        if self.export_prompt {
+            self.export_prompt = false;
+            self.handle_export_key(key);
+            return;
+        }
+
+        if self.note_input.is_some() {
+            self.handle_note_key(key);
+            return;
+        }
+
+        if self.search_input {
+            self.handle_search_key(key);
+            return;
+        }
+
+        let action = self.keymap.get(&key);
+
+        if self.show_errors || self.show_logs {
+            self.handle_panel_key(key, action);
+            return;
+        }
+
+        if self.show_help {
+            if matches!(action, Some(Action::Help | Action::Quit)) || key.code == KeyCode::Esc {
+                self.show_help = false;
+            }
+            return;
+        }
+
+        match action {
+            Some(action) => self.dispatch(action, message_tx),
+            None if key.code == KeyCode::Esc && !self.search.is_empty() => {
+                self.search.clear();
+                if self.history_mode {
+                    self.search_history();
+                }
+                self.select_offset(0);
+            }
+            None => (),
+        }
+    }
+
+    fn dispatch(&mut self, action: Action, message_tx: &mpsc::UnboundedSender<Message>) {
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Next => self.select_offset(1),
+            Action::Prev => self.select_offset(-1),
+            Action::First => self.select_offset(isize::MIN),
+            Action::Last => self.select_offset(isize::MAX),
+            Action::PageDown => self.select_offset(PAGE_SIZE),
+            Action::PageUp => self.select_offset(-PAGE_SIZE),
+            Action::Open => {
+                if let Some(stored) = self.selected_job() {
+                    let link = stored.post.link.clone();
+                    if let Err(err) = open::that_detached(&link) {
+                        self.report_error(&format!("opening {}", link), err.into());
+                    }
+                }
+            }
+            Action::Star => {
+                if let Some(id) = self.selected_id() {
+                    self.store.toggle_starred(&id);
+                    self.save_store();
+                }
+            }
+            Action::Hide => {
+                if let Some(id) = self.selected_id() {
+                    if let Some(hidden) = self.store.toggle_hidden(&id) {
+                        self.status =
+                            if hidden { "job hidden" } else { "job unhidden" }.to_string();
+                    }
+                    self.save_store();
+                    self.select_offset(0);
+                }
+            }
+            Action::Note => {
+                if let Some(stored) = self.selected_job() {
+                    self.note_input = Some(stored.note.clone().unwrap_or_default());
+                }
+            }
+            Action::NextView => {
+                self.view = self.view.next();
+                self.list_state.select(Some(0));
+                self.select_offset(0);
+            }
+            Action::Board => {
+                self.board = !self.board;
+                self.select_offset(0);
+            }
+            Action::History => {
+                self.history_mode = !self.history_mode;
+                if self.history_mode {
+                    self.search_history();
+                }
+                self.list_state.select(Some(0));
+                self.select_offset(0);
+            }
+            Action::Left | Action::Right if self.board => {
+                let index = Stage::ALL
+                    .iter()
+                    .position(|stage| *stage == self.board_stage)
+                    .unwrap_or(0);
+                let index = match action {
+                    Action::Left => index.saturating_sub(1),
+                    _ => (index + 1).min(Stage::ALL.len() - 1),
+                };
+                self.board_stage = Stage::ALL[index];
+                self.board_state.select(Some(0));
+                self.select_offset(0);
+            }
+            Action::Left | Action::Right => (),
+            Action::Advance => self.move_selected(|pipeline| pipeline.stage().advance()),
+            Action::Retreat => self.move_selected(Pipeline::retreat),
+            Action::MarkLost => self.move_selected(|_| Stage::Lost),
+            Action::Draft => self.pending_draft = self.selected_id(),
+            Action::CopyDraft => self.copy_draft(),
+            Action::Search => {
+                self.search_input = true;
+                self.search.clear();
+            }
+            Action::Refresh => self.refresh(message_tx),
+            Action::ToggleExpired => {
+                self.show_expired = !self.show_expired;
+                self.select_offset(0);
+            }
+            Action::ToggleRank => {
+                self.rank = !self.rank;
+                self.select_offset(0);
+            }
+            Action::Export => {
+                self.export_prompt = true;
+                self.status = "export as (c)sv, (m)arkdown, check(l)ist or (j)son".to_string();
+            }
+            Action::ErrorLog => {
+                self.show_errors = true;
+                self.panel_scroll = 0;
+            }
+            Action::Logs => {
+                self.show_logs = true;
+                self.panel_scroll = 0;
+            }
+            Action::Help => self.show_help = true,
+        }
+    }
+
+    fn handle_panel_key(&mut self, key: KeyEvent, action: Option<Action>) {
+        match action {
+            Some(Action::Next) => self.panel_scroll = self.panel_scroll.saturating_add(1),
+            Some(Action::Prev) => self.panel_scroll = self.panel_scroll.saturating_sub(1),
+            Some(Action::PageDown) => {
+                self.panel_scroll = self.panel_scroll.saturating_add(PAGE_SIZE as u16)
+            }
+            Some(Action::PageUp) => {
+                self.panel_scroll = self.panel_scroll.saturating_sub(PAGE_SIZE as u16)
+            }
+            Some(Action::NextView) if self.show_logs => {
+                self.log_level = match self.log_level {
+                    Level::ERROR => Level::WARN,
+                    Level::WARN => Level::INFO,
+                    Level::INFO => Level::DEBUG,
+                    Level::DEBUG => Level::TRACE,
+                    _ => Level::ERROR,
+                };
+                self.panel_scroll = 0;
+            }
+            Some(Action::ErrorLog | Action::Logs | Action::Quit) => {
+                self.show_errors = false;
+                self.show_logs = false;
+            }
+            _ if key.code == KeyCode::Esc => {
+                self.show_errors = false;
+                self.show_logs = false;
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.search_input = false,
+            KeyCode::Esc => {
+                self.search_input = false;
+                self.search.clear();
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => (),
+        }
+
+        if self.history_mode {
+            self.search_history();
+        }
+        self.list_state.select(Some(0));
+    }
+
+    fn move_selected(&mut self, to: fn(&Pipeline) -> Stage) {
+        let Some((id, stage)) = self
+            .selected_job()
+            .map(|stored| (stored.post.id().to_owned(), to(&stored.pipeline)))
+        else {
+            return;
+        };
+
+        if self.store.set_stage(&id, stage, Utc::now().timestamp()) {
+            self.status = format!("moved to {}", stage.label());
+            self.save_store();
+            self.select_offset(0);
+        }
+    }
+
+    fn edit_draft(&mut self, id: &str) {
+        let Some(stored) = self.store.get(id) else {
+            return;
+        };
+
+        let draft = stored
+            .draft
+            .clone()
+            .unwrap_or_else(|| self.config.proposal.initial_draft(&stored.post));
+        let editor = self.config.proposal.editor();
+
+        match proposal::edit(&editor, id, &draft) {
+            Ok(draft) => {
+                self.store.set_draft(id, draft);
+                self.save_store();
+                self.status = "draft saved".to_string();
+            }
+            Err(err) => self.report_error("draft not saved", err),
+        }
+    }
+
+    fn copy_draft(&mut self) {
+        let Some(draft) = self.selected_job().and_then(|stored| stored.draft.clone()) else {
+            self.status = "no draft for this job yet".to_string();
+            return;
+        };
+
+        let mut stderr = io::stderr();
+        let copied = write!(stderr, "{}", proposal::osc52(&draft)).and_then(|_| stderr.flush());
+
+        match copied {
+            Ok(()) => self.status = "draft copied to clipboard".to_string(),
+            Err(err) => self.report_error("copying draft", err.into()),
+        }
+    }
+
+    fn handle_note_key(&mut self, key: KeyEvent) {
+        let Some(note) = &mut self.note_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Enter => {
+                let note = self.note_input.take().unwrap_or_default();
+                if let Some(id) = self.selected_id() {
+                    self.store.set_note(&id, &note);
+                    self.save_store();
+                }
+                self.select_offset(0);
+            }
+            KeyCode::Esc => self.note_input = None,
+            KeyCode::Backspace => {
+                note.pop();
+            }
+            KeyCode::Char(c) => note.push(c),
+            _ => (),
+        }
+    }
+
+    fn handle_export_key(&mut self, key: KeyEvent) {
+        let format = match key.code {
+            KeyCode::Char('c') => ExportFormat::Csv,
+            KeyCode::Char('m') => ExportFormat::Markdown,
+            KeyCode::Char('l') => ExportFormat::Checklist,
+            KeyCode::Char('j') => ExportFormat::Json,
+            _ => {
+                self.status = "export cancelled".to_string();
+                return;
+            }
+        };
+
+        match self.export_visible(format) {
+            Ok(path) => self.status = format!("exported to {}", path.display()),
+            Err(err) => self.report_error("export failed", err),
+        }
+    }
+
+    fn export_visible(&self, format: ExportFormat) -> eyre::Result<PathBuf> {
+        let path = PathBuf::from(format!(
+            "upwork-jobs-{}.{}",
+            Utc::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ));
+
+        let jobs: Vec<&JobPost> = self
+            .visible_jobs()
+            .into_iter()
+            .map(|stored| &stored.post)
+            .collect();
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        export(&jobs, format, &mut writer)?;
+        writer.flush()?;
+
+        Ok(path)
+    }
+
+    fn select_offset(&mut self, offset: isize) {
+        let len = self.current_jobs().len();
+        let state = self.current_state();
+
+        if len == 0 {
+            state.select(Some(0));
+            return;
+        }
+
+        let current = state.selected().unwrap_or(0) as isize;
+        let next = current.saturating_add(offset).clamp(0, len as isize - 1);
+        state.select(Some(next as usize));
+
+        if let Some(id) = self
+            .selected_job()
+            .map(|stored| stored.post.id().to_owned())
+        {
+            self.store.mark_read(&id);
+        }
+    }
+
+    fn save_store(&mut self) {
+        if let Err(err) = self.store.save(&self.store_path) {
+            self.report_error("saving jobs", err);
+        }
+    }
+
+    /// Shows the error in the status bar and keeps its full report for the
+    /// error log.
+    fn report_error(&mut self, context: &str, err: eyre::Report) {
+        tracing::error!("{}: {:#}", context, err);
+        let logged = self.errors.push(context, &err, Utc::now().timestamp());
+        self.status = format!("{}: {}", logged.context, logged.summary);
+    }
+
+    fn in_flight(&self) -> bool {
+        self.fetches.values().any(|status| status.in_flight)
+    }
+
+    /// Queues a fetch of every search and feed not already in flight; the
+    /// scheduler decides how many run at once and how fast they start.
+    fn refresh(&mut self, message_tx: &mpsc::UnboundedSender<Message>) {
+        if !self.in_flight() {
+            self.new_jobs = 0;
+        }
+
+        for subscription in self.config.subscriptions() {
+            let query = subscription.name().to_owned();
+            let status = self.fetches.entry(query.clone()).or_default();
+            if status.in_flight {
+                continue;
+            }
+            status.in_flight = true;
+            tracing::info!(query, "refreshing");
+
+            let scheduler = self.scheduler.clone();
+            let message_tx = message_tx.clone();
+
+            tokio::spawn(async move {
+                let result = scheduler.run(subscription.fetch()).await;
+                let _ = message_tx.send(Message::Fetched { query, result });
+            });
+        }
+    }
+
+    fn handle_message(&mut self, message: Message, message_tx: &mpsc::UnboundedSender<Message>) {
+        match message {
+            Message::Fetched { query, result } => self.handle_fetched(query, result, message_tx),
+            Message::WebhookFailed(err) => self.report_error("webhook", err),
+            Message::ConfigReloaded(config) => self.reload_config(config),
+        }
+    }
+
+    fn reload_config(&mut self, config: eyre::Result<Box<Config>>) {
+        let reloaded = config.and_then(|config| {
+            let mut config = *config;
+            config.apply_cli(&self.cli)?;
+            let webhook = build_webhook(&config)?;
+            let keymap = Keymap::from_config(&config.keybindings)?;
+            let theme = Theme::from_config(&config.theme)?;
+            let scorer = Scorer::from_config(&config);
+            Ok((config, webhook, keymap, theme, scorer))
+        });
+
+        match reloaded {
+            Ok((config, webhook, keymap, theme, scorer)) => {
+                if config.scoring.rank != self.config.scoring.rank {
+                    self.rank = config.scoring.rank;
+                }
+                if config.fetch != self.config.fetch {
+                    self.scheduler = Scheduler::new(&config.fetch);
+                }
+                self.fetches.retain(|query, status| {
+                    status.in_flight || config.source_names().any(|name| name == query)
+                });
+                self.config = config;
+                self.webhook = webhook;
+                self.keymap = keymap;
+                self.theme = theme;
+                self.scorer = scorer;
+                self.duplicates = detect_duplicates(&self.store, &self.config);
+                self.status = "config reloaded".to_string();
+                self.select_offset(0);
+            }
+            Err(err) => self.report_error("config not reloaded", err),
+        }
+    }
+
+    fn handle_fetched(
+        &mut self,
+        query: String,
+        result: eyre::Result<Vec<JobPost>>,
+        message_tx: &mpsc::UnboundedSender<Message>,
+    ) {
+        let now = Utc::now().timestamp();
+        self.fetches
+            .entry(query.clone())
+            .or_default()
+            .finish(&result, now);
+
+        match result {
+            Ok(posts) => {
+                let recorded = self.history.record(posts.iter().map(|post| (post, now)));
+                let new_ids = self
+                    .store
+                    .insert_retained(posts, &self.config.retention, now);
+                self.new_jobs += new_ids.len();
+                tracing::info!(query, new = new_ids.len(), "stored fetched jobs");
+                self.store.prune(&self.config.retention, now);
+                self.duplicates = detect_duplicates(&self.store, &self.config);
+                match recorded {
+                    Ok(()) => self.status = format!("{}: {} new jobs", query, new_ids.len()),
+                    Err(err) => self.report_error(&format!("{}: recording history", query), err),
+                }
+
+                self.forward_new_jobs(&new_ids, now, message_tx);
+                self.save_store();
+            }
+            Err(err) => self.report_error(&query, err),
+        }
+
+        self.select_offset(0);
+    }
+
+    fn forward_new_jobs(
+        &self,
+        new_ids: &[String],
+        now: i64,
+        message_tx: &mpsc::UnboundedSender<Message>,
+    ) {
+        let Some(webhook) = self.webhook.clone() else {
+            return;
+        };
+
+        let jobs: Vec<JobPost> = new_ids
+            .iter()
+            .filter_map(|id| self.store.get(id))
+            .map(|stored| &stored.post)
+            .filter(|post| self.config.filters.matches(post, now))
+            .cloned()
+            .collect();
+
+        if jobs.is_empty() {
+            return;
+        }
+
+        let message_tx = message_tx.clone();
+
+        tokio::spawn(async move {
+            for err in webhook.send_all(&jobs).await {
+                let _ = message_tx.send(Message::WebhookFailed(err));
+            }
+        });
+    }
+}
+
+fn matches_search(post: &JobPost, search: &str) -> bool {
+    post.title.to_lowercase().contains(search)
+        || post
+            .detail
+            .get("details")
+            .is_some_and(|details| details.to_lowercase().contains(search))
+}
+
+fn detect_duplicates(store: &JobStore, config: &Config) -> Duplicates {
+    Duplicates::detect(
+        store.iter().map(|stored| &stored.post),
+        config.dedup.threshold,
+    )
+}
+
+fn build_webhook(config: &Config) -> eyre::Result<Option<Arc<Webhook>>> {
+    config
+        .notifier
+        .clone()
+        .map(|notifier| Webhook::new(notifier).map(Arc::new))
+        .transpose()
+}
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+
+use color_eyre::eyre;
+
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedError {
+    pub at: i64,
+    /// What was being done, e.g. the search that failed to fetch
+    pub context: String,
+    /// The error and its causes on one line
+    pub summary: String,
+    /// The full color-eyre report, without terminal colors
+    pub report: String,
+}
+
+/// The most recent failures, newest first, so the status bar can show the
+/// latest one and the error panel the rest.
+#[derive(Debug, Default)]
+pub struct ErrorLog {
+    entries: VecDeque<LoggedError>,
+}
+
+impl ErrorLog {
+    pub fn push(&mut self, context: &str, err: &eyre::Report, at: i64) -> &LoggedError {
+        self.entries.truncate(MAX_ENTRIES - 1);
+        self.entries.push_front(LoggedError {
+            at,
+            context: context.to_owned(),
+            summary: format!("{:#}", err),
+            report: strip_ansi(&format!("{:?}", err)),
+        });
+
+        &self.entries[0]
+    }
+
+    pub fn latest(&self) -> Option<&LoggedError> {
+        self.entries.front()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LoggedError> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Drops the ANSI escape sequences color-eyre colors its reports with.
+fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            plain.push(c);
+            continue;
+        }
+
+        // CSI sequences end at the first byte in `@`..=`~` after the `[`.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::{eyre, WrapErr};
+
+    #[test]
+    fn test_keeps_newest_errors() {
+        let mut log = ErrorLog::default();
+
+        for at in 0..MAX_ENTRIES as i64 + 5 {
+            log.push("rust", &eyre!("failure {}", at), at);
+        }
+        let err = Err::<(), _>(eyre!("429 Too Many Requests"))
+            .wrap_err("fetching design")
+            .unwrap_err();
+        let latest = log.push("design", &err, 1000).clone();
+
+        assert_eq!(log.len(), MAX_ENTRIES);
+        assert_eq!(log.latest(), Some(&latest));
+        assert_eq!(latest.summary, "fetching design: 429 Too Many Requests");
+        assert!(latest.report.contains("429 Too Many Requests"));
+        assert_eq!(log.iter().last().map(|entry| entry.at), Some(6));
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\u{1b}[31mError:\u{1b}[0m\n   0: \u{1b}[1;91mboom\u{1b}[0m"),
+            "Error:\n   0: boom"
+        );
+    }
+}
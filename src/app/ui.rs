@@ -0,0 +1,646 @@
+use std::{cmp::Reverse, collections::BTreeMap, time::Instant};
+
+use chrono::{DateTime, Utc};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+use tracing::Level;
+
+use super::App;
+use crate::{
+    config::Config,
+    core_opt::{
+        price::{CurrencyConfig, Price},
+        price_label,
+    },
+    keymap::Action,
+    pipeline::{conversions, Conversion, ConversionSummary, Stage},
+    scoring::Score,
+    skills::SkillProfile,
+    store::{StoredJob, View},
+    theme::Theme,
+    JobPost,
+};
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(frame.size());
+
+    if app.board {
+        render_board(frame, main, app);
+    } else {
+        render_list(frame, main, app);
+    }
+
+    render_status(frame, status, app);
+
+    if app.show_help {
+        render_help(frame, app);
+    }
+
+    if app.show_errors {
+        render_errors(frame, app);
+    }
+
+    if app.show_logs {
+        render_logs(frame, app);
+    }
+}
+
+fn render_list(frame: &mut Frame, main: Rect, app: &mut App) {
+    let [list_area, detail_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(main);
+
+    let now = Utc::now().timestamp();
+    let theme = &app.theme;
+    let jobs = app.current_jobs();
+
+    let items: Vec<ListItem> = jobs
+        .iter()
+        .map(|stored| {
+            let mut style = theme.list;
+            if !stored.read {
+                style = style.patch(theme.unread);
+            }
+            if stored.starred {
+                style = style.patch(theme.starred);
+            }
+            if app.config.filters.is_expired(&stored.post, now) {
+                style = style.patch(theme.expired);
+            }
+
+            let marker = match (stored.starred, stored.note.is_some()) {
+                (true, _) => "★",
+                (false, true) => "✎",
+                (false, false) => " ",
+            };
+
+            let score = app.scorer.score(&stored.post, now).total;
+
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(
+                    "{:>4} ",
+                    age_label(now, stored.post.posted_timestamp)
+                )),
+                Span::styled(format!("{:>3.0} ", score), theme.price),
+                Span::styled(skill_gauge(app, &stored.post), theme.gauge),
+                Span::styled(format!("{} ", marker), theme.starred),
+                Span::styled(restricted_flag(app, &stored.post), theme.restricted),
+                Span::styled(repost_badge(app, &stored.post), theme.starred),
+                Span::styled(stored.post.title.clone(), style),
+                Span::styled(
+                    format!(" {}", short_price(&stored.post, &app.config.currency)),
+                    theme.price,
+                ),
+            ]))
+            .style(theme.list)
+        })
+        .collect();
+
+    let mut title = if app.history_mode {
+        format!(" History ({}", jobs.len())
+    } else {
+        format!(" Jobs ({}", jobs.len())
+    };
+    if !app.history_mode {
+        if app.view != View::All {
+            title.push_str(&format!(", {}", app.view.label()));
+        }
+        if app.rank {
+            title.push_str(", by score");
+        }
+        if app.show_expired {
+            title.push_str(", showing expired");
+        }
+    }
+    if !app.search.is_empty() {
+        title.push_str(&format!(", matching \"{}\"", app.search));
+    }
+    title.push_str(") ");
+
+    let list = List::new(items)
+        .block(bordered(title, theme))
+        .highlight_style(theme.selection);
+
+    let selected = app.selected_job();
+    let score = selected.map(|stored| app.scorer.score(&stored.post, now));
+    let reposts = selected.and_then(|stored| repost_line(app, &stored.post));
+    let detail = detail_paragraph(selected, score, reposts, &app.config, theme);
+
+    frame.render_stateful_widget(list, list_area, &mut app.list_state);
+    frame.render_widget(detail, detail_area);
+}
+
+fn render_board(frame: &mut Frame, main: Rect, app: &mut App) {
+    let [columns_area, summary_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(5)]).areas(main);
+    let column_areas =
+        Layout::horizontal(Stage::ALL.map(|_| Constraint::Ratio(1, Stage::ALL.len() as u32)))
+            .split(columns_area);
+
+    let theme = &app.theme;
+
+    for (stage, area) in Stage::ALL.into_iter().zip(column_areas.iter()) {
+        let jobs = app.store.in_stage(stage);
+        let items: Vec<ListItem> = jobs
+            .iter()
+            .map(|stored| {
+                let style = if stored.starred {
+                    theme.list.patch(theme.starred)
+                } else {
+                    theme.list
+                };
+                ListItem::new(stored.post.title.clone()).style(style)
+            })
+            .collect();
+
+        let mut block = bordered(format!(" {} ({}) ", stage.label(), jobs.len()), theme);
+        if stage == app.board_stage {
+            block = block.border_style(theme.border.patch(theme.selection));
+        }
+        let list = List::new(items).block(block);
+
+        if stage == app.board_stage {
+            frame.render_stateful_widget(
+                list.highlight_style(theme.selection),
+                *area,
+                &mut app.board_state,
+            );
+        } else {
+            frame.render_widget(list, *area);
+        }
+    }
+
+    frame.render_widget(
+        summary_paragraph(&conversions(app.store.iter()), theme),
+        summary_area,
+    );
+}
+
+fn summary_paragraph(summary: &ConversionSummary, theme: &Theme) -> Paragraph<'static> {
+    let rate = |conversion: &Conversion| match conversion.win_rate() {
+        Some(rate) => format!("{}/{} ({:.0}%)", conversion.won, conversion.applied, rate),
+        None => "-".to_string(),
+    };
+    let top = |label: &str, by_key: &BTreeMap<String, Conversion>| {
+        let mut entries: Vec<_> = by_key.iter().collect();
+        entries.sort_by_key(|(_, conversion)| Reverse(conversion.applied));
+
+        let entries: Vec<String> = entries
+            .iter()
+            .take(5)
+            .map(|(key, conversion)| format!("{} {}", key, rate(conversion)))
+            .collect();
+
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", label),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(entries.join(", ")),
+        ])
+    };
+
+    let total = &summary.total;
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Won: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                "{}, {} applied, {} interviewed",
+                rate(total),
+                total.applied,
+                total.interviewing
+            )),
+        ]),
+        top("Categories", &summary.by_category),
+        top("Skills", &summary.by_skill),
+    ];
+
+    Paragraph::new(lines).block(bordered(" Conversion ".to_string(), theme))
+}
+
+fn render_status(frame: &mut Frame, status: Rect, app: &App) {
+    let [message_area, fetch_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(status);
+
+    let status_line = if let Some(note) = &app.note_input {
+        Line::from(format!("note: {}", note))
+    } else if app.search_input {
+        Line::from(format!("/{}", app.search))
+    } else {
+        let mut hints = app
+            .keymap
+            .key_for(Action::Help)
+            .map(|key| format!("  {} help", key))
+            .unwrap_or_default();
+        if !app.errors.is_empty() {
+            if let Some(key) = app.keymap.key_for(Action::ErrorLog) {
+                hints.push_str(&format!("  {} errors ({})", key, app.errors.len()));
+            }
+        }
+
+        Line::from(vec![
+            Span::raw(app.status.clone()),
+            Span::styled(hints, Style::default().add_modifier(Modifier::DIM)),
+        ])
+    };
+
+    frame.render_widget(Paragraph::new(status_line), message_area);
+    frame.render_widget(Paragraph::new(fetch_line(app)), fetch_area);
+}
+
+/// Each search and feed with its job count and age of the last good fetch, then the
+/// new and unread counts, the time to the next poll and the latest error.
+fn fetch_line(app: &App) -> Line<'static> {
+    let now = Utc::now().timestamp();
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let spinner = SPINNER[app.spinner % SPINNER.len()].to_string();
+    let mut spans = Vec::new();
+
+    for query in app.config.source_names() {
+        let status = app.fetches.get(query).cloned().unwrap_or_default();
+        let fetched = status
+            .last_fetched
+            .map(|fetched| format!("{} {}", status.items, age_label(now, fetched)));
+
+        spans.push(Span::raw(format!("{} ", query)));
+        if status.in_flight {
+            spans.push(Span::raw(spinner.clone()));
+        } else if status.last_error.is_some() {
+            spans.push(Span::styled("✗", app.theme.missing));
+        } else {
+            spans.push(Span::styled(fetched.unwrap_or("-".to_string()), dim));
+        }
+        spans.push(Span::raw("  "));
+    }
+
+    let unread = app
+        .store
+        .iter()
+        .filter(|stored| !stored.read && !stored.hidden)
+        .count();
+    let next_poll = app.next_poll.saturating_duration_since(Instant::now());
+    spans.push(Span::raw(format!(
+        "│ {} new · {} unread │ next {}:{:02} ",
+        app.new_jobs,
+        unread,
+        next_poll.as_secs() / 60,
+        next_poll.as_secs() % 60
+    )));
+
+    if let Some(error) = app.errors.latest() {
+        let at = DateTime::from_timestamp(error.at, 0)
+            .map(|at| at.format("%H:%M").to_string())
+            .unwrap_or_default();
+        spans.push(Span::raw("│ "));
+        spans.push(Span::styled(
+            format!("✗ {} {}: {}", at, error.context, error.summary),
+            app.theme.missing,
+        ));
+    }
+
+    Line::from(spans)
+}
+
+fn render_help(frame: &mut Frame, app: &App) {
+    let help = app.keymap.help();
+    let key_width = help.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0);
+
+    let lines: Vec<Line> = help
+        .iter()
+        .map(|(keys, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>width$}  ", keys, width = key_width),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(action.description()),
+            ])
+        })
+        .collect();
+
+    let area = centered(frame.size(), 60, lines.len() as u16 + 2);
+    let popup = Paragraph::new(lines).block(bordered(" Keys ".to_string(), &app.theme));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_errors(frame: &mut Frame, app: &App) {
+    let mut lines = Vec::new();
+
+    for error in app.errors.iter() {
+        let at = DateTime::from_timestamp(error.at, 0)
+            .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(at, Style::default().add_modifier(Modifier::DIM)),
+            Span::raw(" "),
+            Span::styled(error.context.clone(), app.theme.missing),
+        ]));
+        lines.extend(
+            error
+                .report
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+        lines.push(Line::default());
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from("No errors since starting"));
+    }
+
+    let size = frame.size();
+    let area = centered(size, size.width * 9 / 10, size.height * 8 / 10);
+    let title = format!(" Errors ({}) ", app.errors.len());
+    let popup = Paragraph::new(lines)
+        .block(bordered(title, &app.theme))
+        .wrap(Wrap { trim: false })
+        .scroll((app.panel_scroll, 0));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_logs(frame: &mut Frame, app: &App) {
+    let records = app.logs.at_least(app.log_level);
+    let dim = Style::default().add_modifier(Modifier::DIM);
+
+    let mut lines: Vec<Line> = records
+        .iter()
+        .map(|record| {
+            let at = DateTime::from_timestamp(record.at, 0)
+                .map(|at| at.format("%H:%M:%S").to_string())
+                .unwrap_or_default();
+            let style = match record.level {
+                Level::ERROR | Level::WARN => app.theme.missing,
+                Level::INFO => Style::default(),
+                _ => dim,
+            };
+
+            Line::from(vec![
+                Span::styled(format!("{} ", at), dim),
+                Span::styled(format!("{:<5} ", record.level), style),
+                Span::styled(format!("{} ", record.target), dim),
+                Span::styled(record.message.clone(), style),
+            ])
+        })
+        .collect();
+
+    if lines.is_empty() {
+        lines.push(Line::from("Nothing logged at this level yet"));
+    }
+
+    let size = frame.size();
+    let area = centered(size, size.width * 9 / 10, size.height * 8 / 10);
+    let title = format!(" Log ({} and above) ", app.log_level);
+    let popup = Paragraph::new(lines)
+        .block(bordered(title, &app.theme))
+        .wrap(Wrap { trim: false })
+        .scroll((app.panel_scroll, 0));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    area
+}
+
+fn bordered(title: String, theme: &Theme) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(title)
+}
+
+fn detail_paragraph(
+    stored: Option<&StoredJob>,
+    score: Option<Score>,
+    reposts: Option<(&'static str, String)>,
+    config: &Config,
+    theme: &Theme,
+) -> Paragraph<'static> {
+    let rules = &config.filters;
+    let block = bordered(" Detail ".to_string(), theme);
+
+    let Some(stored) = stored else {
+        return Paragraph::new("No jobs").block(block);
+    };
+
+    let job = &stored.post;
+    let styled_field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", label),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(value, style),
+        ])
+    };
+    let field = |label: &str, value: String| styled_field(label, value, Style::default());
+
+    let detail = |key: &str| job.detail.get(key).cloned().unwrap_or_default();
+
+    let mut lines = vec![
+        Line::styled(
+            job.title.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+        styled_field(
+            "Price",
+            Price::from_job(job)
+                .map(|price| price.render(&config.currency))
+                .unwrap_or_else(|| price_label(job)),
+            theme.price,
+        ),
+        field("Category", detail("Category")),
+        field("Posted On", job.posted_on.clone()),
+        field("Country", job.country.clone().unwrap_or_default()),
+        skills_line(job, &config.skills, theme),
+        field("Link", job.link.clone()),
+    ];
+
+    if let Some(score) = score {
+        let parts: Vec<String> = score
+            .parts
+            .iter()
+            .map(|part| format!("{} {:.0}% ×{}", part.name, part.value * 100.0, part.weight))
+            .collect();
+        lines.push(field(
+            "Score",
+            format!("{:.0} ({})", score.total, parts.join(", ")),
+        ));
+    }
+    if let Some((label, value)) = reposts {
+        lines.push(styled_field(label, value, theme.starred));
+    }
+    if let Some(requirement) = &job.location_requirement {
+        let style = if rules.is_restricted(job) {
+            theme.restricted
+        } else {
+            Style::default()
+        };
+        lines.push(styled_field("Location", requirement.text.clone(), style));
+    }
+    if let Some(transition) = stored.pipeline.history().last() {
+        let since = DateTime::from_timestamp(transition.at, 0)
+            .map(|at| at.format(" since %Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        lines.push(field(
+            "Stage",
+            format!("{}{}", transition.stage.label(), since),
+        ));
+    }
+
+    if let Some(draft) = &stored.draft {
+        lines.push(field(
+            "Draft",
+            format!("{} words", draft.split_whitespace().count()),
+        ));
+    }
+    if let Some(note) = &stored.note {
+        lines.push(styled_field("Note", note.clone(), theme.starred));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::raw(detail("details")));
+
+    Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+}
+
+const GAUGE_WIDTH: usize = 5;
+
+/// A five-cell bar of the team's match with the job's skills.
+fn skill_gauge(app: &App, job: &JobPost) -> String {
+    if app.config.skills.is_empty() {
+        return String::new();
+    }
+
+    let ratio = app.config.skills.matching(job).ratio();
+    let filled = (ratio * GAUGE_WIDTH as f64).round() as usize;
+
+    format!(
+        "{}{} ",
+        "▰".repeat(filled),
+        "▱".repeat(GAUGE_WIDTH - filled)
+    )
+}
+
+fn skills_line(job: &JobPost, profile: &SkillProfile, theme: &Theme) -> Line<'static> {
+    let label = |text: &str| {
+        Span::styled(
+            format!("{}: ", text),
+            Style::default().add_modifier(Modifier::BOLD),
+        )
+    };
+
+    if profile.is_empty() {
+        let skills = job.detail.get("Skills").cloned().unwrap_or_default();
+        return Line::from(vec![label("Skills"), Span::raw(skills)]);
+    }
+
+    let matched = profile.matching(job);
+    let mut spans = vec![
+        label("Skills"),
+        Span::styled(
+            format!("{:.0}% match ", matched.ratio() * 100.0),
+            theme.gauge,
+        ),
+    ];
+    let known = matched
+        .known
+        .iter()
+        .map(|(skill, level)| Span::raw(format!("{} ({})", skill, level)));
+    let missing = matched
+        .missing
+        .iter()
+        .map(|skill| Span::styled(skill.to_string(), theme.missing));
+    for (index, span) in known.chain(missing).enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(", "));
+        }
+        spans.push(span);
+    }
+
+    Line::from(spans)
+}
+
+fn repost_badge(app: &App, job: &JobPost) -> String {
+    match app.duplicates.reposts_of(job.id()).len() {
+        0 => String::new(),
+        count => format!("↻{} ", count),
+    }
+}
+
+/// Describes the job's reposts, or the original when the job is itself one.
+fn repost_line(app: &App, job: &JobPost) -> Option<(&'static str, String)> {
+    if let Some(original) = app.duplicates.original_of(job.id()) {
+        let title = app
+            .store
+            .get(original)
+            .map(|stored| stored.post.title.clone())
+            .unwrap_or_else(|| original.to_owned());
+        return Some(("Repost of", title));
+    }
+
+    let reposts = app.duplicates.reposts_of(job.id());
+    let latest = reposts.last()?;
+    let times = if reposts.len() == 1 { "time" } else { "times" };
+    let posted_on = app
+        .store
+        .get(latest)
+        .map(|stored| format!(", latest {}", stored.post.posted_on))
+        .unwrap_or_default();
+
+    Some((
+        "Reposted",
+        format!("{} {}{}", reposts.len(), times, posted_on),
+    ))
+}
+
+fn restricted_flag(app: &App, job: &JobPost) -> &'static str {
+    if app.config.filters.is_restricted(job) {
+        "⊘ "
+    } else {
+        ""
+    }
+}
+
+fn short_price(job: &JobPost, currency: &CurrencyConfig) -> String {
+    if let Some(price) = Price::from_job(job) {
+        return price.convert(currency).unwrap_or(price).to_string();
+    }
+
+    match (job.detail.get("Budget"), job.detail.get("Hourly Range")) {
+        (Some(budget), _) => budget.clone(),
+        (None, Some(hourly)) => format!("{}/h", hourly),
+        (None, None) => String::new(),
+    }
+}
+
+fn age_label(now: i64, timestamp: i64) -> String {
+    let minutes = (now - timestamp).max(0) / 60;
+
+    match minutes {
+        0..=59 => format!("{}m", minutes),
+        60..=1439 => format!("{}h", minutes / 60),
+        _ => format!("{}d", minutes / 1440),
+    }
+}
@@ -0,0 +1,143 @@
+use std::{net::IpAddr, path::PathBuf, time::Duration};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::{export::ExportFormat, report::ReportFormat, store::View};
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Config file, defaults to `$XDG_CONFIG_HOME/upwork-tui/config.toml`
+    #[arg(short, long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Upwork search query to poll, can be repeated; replaces `searches` from the config
+    #[arg(short, long = "search", value_name = "QUERY", global = true)]
+    pub searches: Vec<String>,
+
+    /// Hide jobs posted longer ago than this, e.g. `48h` or `2d`
+    #[arg(long, value_parser = humantime::parse_duration, global = true)]
+    pub max_age: Option<Duration>,
+
+    /// Drop jobs from the store once they are older than this
+    #[arg(long, value_parser = humantime::parse_duration, global = true)]
+    pub retention: Option<Duration>,
+
+    /// Include expired jobs in the list
+    #[arg(long, global = true)]
+    pub show_expired: bool,
+
+    #[arg(long, value_parser = humantime::parse_duration, global = true)]
+    pub poll_interval: Option<Duration>,
+
+    /// POST new matching jobs as JSON to this URL
+    #[arg(long)]
+    pub webhook_url: Option<String>,
+
+    /// Extra webhook request header as `Name: value`, can be repeated
+    #[arg(long = "webhook-header", value_name = "HEADER", value_parser = parse_header)]
+    pub webhook_headers: Vec<(String, String)>,
+
+    /// File holding the webhook payload template, with placeholders like `{{title}}`
+    #[arg(long, value_name = "FILE")]
+    pub webhook_template: Option<PathBuf>,
+
+    #[arg(long)]
+    pub webhook_retries: Option<u32>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Write the filtered job list from the store to a file or stdout
+    Export(ExportArgs),
+    /// Re-publish each search, after filtering, as RSS and Atom feeds over HTTP
+    Serve(ServeArgs),
+    /// Full-text search every job ever fetched, beyond the store's retention
+    Search(SearchArgs),
+    /// Summarise job history by day, category, skill and price
+    Report(ReportArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    #[arg(short, long, value_enum, default_value = "csv")]
+    pub format: ExportFormat,
+
+    /// Which jobs to export, e.g. `starred`
+    #[arg(long, value_enum, default_value = "all")]
+    pub view: View,
+
+    /// Output file, defaults to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// Words to find in titles and descriptions; `word*` matches by prefix
+    pub query: Vec<String>,
+
+    /// Only jobs asking for this skill, e.g. `Rust`
+    #[arg(long)]
+    pub skill: Option<String>,
+
+    #[arg(short = 'n', long, default_value_t = 50)]
+    pub limit: usize,
+
+    #[arg(short, long, value_enum, default_value = "markdown")]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// How far back to report, e.g. `30d`
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "30d")]
+    pub since: Duration,
+
+    #[arg(short, long, value_enum, default_value = "table")]
+    pub format: ReportFormat,
+
+    /// How many categories and skills to list
+    #[arg(long, default_value_t = 15)]
+    pub top: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: IpAddr,
+}
+
+fn parse_header(header: &str) -> Result<(String, String), String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("`{}` is not in `Name: value` form", header))?;
+
+    Ok((name.trim().to_owned(), value.trim().to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            parse_header("Authorization: Bearer abc:def"),
+            Ok(("Authorization".to_string(), "Bearer abc:def".to_string()))
+        );
+        assert!(parse_header("no-colon").is_err());
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use color_eyre::{
+    eyre::{self, bail, eyre, WrapErr},
+    Section,
+};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+
+use crate::{
+    cli::Cli,
+    core_opt::{
+        filter::FilterRules,
+        price::CurrencyConfig,
+        source::{FeedConfig, Subscription},
+    },
+    dedup::DedupConfig,
+    fetch::FetchConfig,
+    keymap::{KeybindingConfig, Keymap},
+    logging::LogConfig,
+    proposal::ProposalConfig,
+    scoring::ScoringConfig,
+    skills::SkillProfile,
+    store::Retention,
+    theme::{Theme, ThemeConfig},
+    webhook::{Webhook, WebhookConfig},
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const MIN_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub searches: Vec<String>,
+    /// RSS or Atom feeds of other job boards
+    pub feeds: Vec<FeedConfig>,
+    #[serde(with = "humantime_serde")]
+    pub poll_interval: Duration,
+    pub filters: FilterRules,
+    pub retention: Retention,
+    pub notifier: Option<WebhookConfig>,
+    pub keybindings: KeybindingConfig,
+    pub theme: ThemeConfig,
+    pub proposal: ProposalConfig,
+    pub currency: CurrencyConfig,
+    pub skills: SkillProfile,
+    pub scoring: ScoringConfig,
+    pub dedup: DedupConfig,
+    pub fetch: FetchConfig,
+    pub log: LogConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            searches: Vec::new(),
+            feeds: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            filters: FilterRules::default(),
+            retention: Retention::default(),
+            notifier: None,
+            keybindings: KeybindingConfig::default(),
+            theme: ThemeConfig::default(),
+            proposal: ProposalConfig::default(),
+            currency: CurrencyConfig::default(),
+            skills: SkillProfile::default(),
+            scoring: ScoringConfig::default(),
+            dedup: DedupConfig::default(),
+            fetch: FetchConfig::default(),
+            log: LogConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// The searches followed by the feeds.
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        let searches = self.searches.iter().cloned().map(Subscription::Search);
+        let feeds = self.feeds.iter().cloned().map(Subscription::Feed);
+
+        searches.chain(feeds).collect()
+    }
+
+    /// The searches followed by the feeds, as their fetches are keyed.
+    pub fn source_names(&self) -> impl Iterator<Item = &str> {
+        self.searches
+            .iter()
+            .map(String::as_str)
+            .chain(self.feeds.iter().map(|feed| feed.name.as_str()))
+    }
+
+    pub fn default_path() -> eyre::Result<PathBuf> {
+        let config_dir = dirs::config_dir().ok_or_else(|| eyre!("config directory not found"))?;
+        Ok(config_dir.join("upwork-tui").join("config.toml"))
+    }
+
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read config {}", path.display()))?;
+
+        Self::parse(&data)
+            .wrap_err_with(|| format!("invalid config {}", path.display()))
+            .suggestion("fix the reported field, or remove the file to use the defaults")
+    }
+
+    pub fn parse(data: &str) -> eyre::Result<Self> {
+        let config: Self = toml::from_str(data)?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> eyre::Result<()> {
+        if let Some(search) = self.searches.iter().find(|s| s.trim().is_empty()) {
+            bail!("searches: `{}` is an empty search", search);
+        }
+
+        for feed in &self.feeds {
+            feed.validate()
+                .wrap_err_with(|| format!("feeds: `{}`", feed.name))?;
+        }
+
+        let mut names: HashSet<&str> = self.searches.iter().map(String::as_str).collect();
+        if let Some(feed) = self.feeds.iter().find(|feed| !names.insert(&feed.name)) {
+            bail!(
+                "feeds: `{}` is already the name of a search or feed",
+                feed.name
+            );
+        }
+
+        if self.poll_interval < MIN_POLL_INTERVAL {
+            bail!(
+                "poll_interval: must be at least {}",
+                humantime::format_duration(MIN_POLL_INTERVAL)
+            );
+        }
+
+        if let Some(max_age) = self.filters.max_age {
+            if max_age > self.retention.max_age {
+                bail!(
+                    "filters.max_age ({}) is longer than retention.max_age ({}), jobs would be pruned before they expire",
+                    humantime::format_duration(max_age),
+                    humantime::format_duration(self.retention.max_age)
+                );
+            }
+        }
+
+        if let Some(notifier) = &self.notifier {
+            Webhook::new(notifier.clone()).wrap_err("notifier")?;
+        }
+
+        self.currency.validate().wrap_err("currency")?;
+        self.skills.validate().wrap_err("skills")?;
+        self.scoring.validate().wrap_err("scoring")?;
+        self.dedup.validate().wrap_err("dedup")?;
+        self.fetch.validate().wrap_err("fetch")?;
+        self.log.validate().wrap_err("log")?;
+        Keymap::from_config(&self.keybindings)?;
+        Theme::from_config(&self.theme).wrap_err("theme")?;
+
+        Ok(())
+    }
+
+    pub fn apply_cli(&mut self, cli: &Cli) -> eyre::Result<()> {
+        if !cli.searches.is_empty() {
+            self.searches = cli.searches.clone();
+        }
+
+        if let Some(max_age) = cli.max_age {
+            self.filters.max_age = Some(max_age);
+        }
+
+        if let Some(retention) = cli.retention {
+            self.retention.max_age = retention;
+        }
+
+        if let Some(poll_interval) = cli.poll_interval {
+            self.poll_interval = poll_interval;
+        }
+
+        if let Some(url) = &cli.webhook_url {
+            let notifier = self.notifier.get_or_insert_with(WebhookConfig::default);
+            notifier.url = url.clone();
+        }
+
+        if let Some(notifier) = &mut self.notifier {
+            notifier.headers.extend(cli.webhook_headers.iter().cloned());
+
+            if let Some(path) = &cli.webhook_template {
+                let template = fs::read_to_string(path).wrap_err_with(|| {
+                    format!("failed to read webhook template {}", path.display())
+                })?;
+                notifier.template = Some(template);
+            }
+
+            if let Some(retries) = cli.webhook_retries {
+                notifier.retries = retries;
+            }
+        }
+
+        self.validate()
+    }
+}
+
+pub fn watch<F>(path: &Path, on_change: F) -> eyre::Result<RecommendedWatcher>
+where
+    F: Fn(eyre::Result<Config>) + Send + 'static,
+{
+    let config_path = path.to_owned();
+    let watch_dir = path
+        .parent()
+        .ok_or_else(|| eyre!("config path {} has no parent", path.display()))?;
+    fs::create_dir_all(watch_dir)?;
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+
+        let touches_config = event.paths.iter().any(|p| p == &config_path);
+        let changed = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+
+        if touches_config && changed {
+            on_change(Config::load(&config_path));
+        }
+    })?;
+
+    watcher.watch(watch_dir, RecursiveMode::NonRecursive)?;
+
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core_opt::source::SourceKind, keymap::Preset};
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_parse_full_config() -> eyre::Result<()> {
+        let config = Config::parse(
+            r#"
+            searches = ["rust", "tokio"]
+            poll_interval = "10m"
+
+            [[feeds]]
+            name = "remote"
+            url = "https://remote.example.com/jobs.rss"
+
+            [[feeds]]
+            name = "upwork saved"
+            url = "https://www.upwork.com/ab/feed/jobs/rss?q=rust&sort=recency"
+            source = "upwork"
+
+            [filters]
+            max_age = "1d"
+            deny_countries = ["Antarctica"]
+            team_countries = ["Germany"]
+
+            [retention]
+            max_age = "60d"
+
+            [notifier]
+            url = "https://chat.example.com/hook"
+            retries = 5
+            headers = { Authorization = "Bearer abc" }
+            template = '{"text": "{{title}}"}'
+
+            [keybindings]
+            preset = "arrows"
+            keys = { "ctrl-r" = "refresh" }
+
+            [theme]
+            name = "solarized"
+            styles = { selection = { bg = "blue" } }
+
+            [proposal]
+            template = "Hi, about {{title}}"
+            editor = "nano"
+
+            [currency]
+            preferred = "EUR"
+            rates = { USD = 0.92, GBP = 1.17 }
+
+            [skills]
+            Rust = 5
+            Tokio = 4
+
+            [scoring]
+            rank = true
+            weights = { skills = 3.0 }
+
+            [dedup]
+            collapse = false
+
+            [fetch]
+            concurrency = 5
+            interval = "500ms"
+
+            [log]
+            level = "upwork_tui=debug"
+            "#,
+        )?;
+
+        assert_eq!(config.searches, vec!["rust", "tokio"]);
+        assert_eq!(
+            config.source_names().collect::<Vec<_>>(),
+            vec!["rust", "tokio", "remote", "upwork saved"]
+        );
+        assert_eq!(
+            config.subscriptions()[2],
+            Subscription::Feed(config.feeds[0].clone())
+        );
+        assert_eq!(config.feeds[0].source, SourceKind::Generic);
+        assert_eq!(config.feeds[1].source, SourceKind::Upwork);
+        assert_eq!(config.poll_interval, Duration::from_secs(600));
+        assert_eq!(config.filters.max_age, Some(Duration::from_secs(86400)));
+        assert_eq!(config.filters.team_countries, vec!["Germany"]);
+        assert_eq!(config.retention.max_age, Duration::from_secs(60 * 86400));
+
+        let notifier = config.notifier.unwrap();
+        assert_eq!(notifier.retries, 5);
+        assert_eq!(notifier.backoff, Duration::from_secs(1));
+        assert_eq!(notifier.headers["Authorization"], "Bearer abc");
+
+        assert_eq!(config.keybindings.preset, Preset::Arrows);
+        assert_eq!(config.theme.name.as_deref(), Some("solarized"));
+        assert_eq!(config.proposal.editor(), "nano");
+        assert_eq!(config.currency.rates["GBP"], 1.17);
+        assert_eq!(config.skills.level("tokio"), Some(4));
+        assert!(config.scoring.rank);
+        assert_eq!(config.scoring.weights.skills, 3.0);
+        assert_eq!(config.scoring.weights.budget, 1.0);
+        assert!(!config.dedup.collapse);
+        assert_eq!(config.dedup.threshold, 0.8);
+        assert_eq!(config.fetch.concurrency, 5);
+        assert_eq!(config.fetch.burst, 3);
+        assert_eq!(config.fetch.interval, Duration::from_millis(500));
+        assert_eq!(config.log.level, "upwork_tui=debug");
+        assert_eq!(config.log.max_files, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_empty_config() -> eyre::Result<()> {
+        assert_eq!(Config::parse("")?, Config::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        assert!(Config::parse("serches = [\"rust\"]").is_err());
+        assert!(Config::parse("poll_interval = \"5s\"").is_err());
+        assert!(Config::parse("searches = [\" \"]").is_err());
+        assert!(Config::parse("[filters]\nmax_age = \"90d\"").is_err());
+        assert!(Config::parse("[[feeds]]\nname = \"a\"\nurl = \"file:///jobs.rss\"").is_err());
+        assert!(Config::parse(
+            "searches = [\"rust\"]\n[[feeds]]\nname = \"rust\"\nurl = \"https://a.example\""
+        )
+        .is_err());
+        assert!(Config::parse(
+            "[[feeds]]\nname = \"a\"\nurl = \"https://a.example\"\nsource = \"indeed\""
+        )
+        .is_err());
+        assert!(Config::parse("[notifier]\nurl = \"ftp://example.com\"").is_err());
+        assert!(Config::parse("[theme]\nname = \"neon\"").is_err());
+        assert!(Config::parse("[currency]\nrates = { USD = -1.0 }").is_err());
+        assert!(Config::parse("[dedup]\nthreshold = 1.5").is_err());
+        assert!(Config::parse("[fetch]\nconcurrency = 0").is_err());
+        assert!(Config::parse("[fetch]\nconcurrency = 65").is_err());
+        assert!(Config::parse("[fetch]\nconcurrency = 9223372036854775807").is_err());
+        assert!(Config::parse("[notifier]\nurl = \"https://a.example\"\nretries = 40").is_err());
+        assert!(Config::parse(
+            "[notifier]\nurl = \"https://a.example\"\ntemplate = '{\"text\": {{title}}}'"
+        )
+        .is_err());
+        assert!(Config::parse("[log]\nmax_files = 0").is_err());
+        assert!(Config::parse("[keybindings]\nkeys = { \"hyper-j\" = \"next\" }").is_err());
+    }
+
+    #[test]
+    fn test_load_reports_path() {
+        let path = std::env::temp_dir().join(format!(
+            "upwork-tui-config-{}-invalid.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "poll_interval = 3").unwrap();
+
+        let err = Config::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(format!("{:?}", err).contains(&path.display().to_string()));
+    }
+
+    #[test]
+    fn test_watch_reloads_on_change() -> eyre::Result<()> {
+        let dir = std::env::temp_dir().join(format!("upwork-tui-watch-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, "searches = [\"rust\"]")?;
+
+        let (tx, rx) = mpsc::channel();
+        let _watcher = watch(&path, move |config| {
+            let _ = tx.send(config);
+        })?;
+
+        fs::write(&path, "searches = [\"tokio\"]")?;
+
+        let reloaded = std::iter::from_fn(|| rx.recv_timeout(Duration::from_secs(5)).ok())
+            .filter_map(Result::ok)
+            .find(|config| config.searches == vec!["tokio"]);
+        fs::remove_dir_all(&dir)?;
+
+        assert!(reloaded.is_some());
+
+        Ok(())
+    }
+}
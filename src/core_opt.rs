@@ -1,12 +1,33 @@
 use bytes::Bytes;
 use color_eyre::eyre;
 
+pub mod filter;
 pub mod get_bytes;
-mod parse_xml;
+pub mod location;
+pub mod parse_xml;
+pub mod price;
+pub mod source;
 
+use get_bytes::req_bytes;
 use parse_xml::parse_xml;
 
-use crate::FinalPost;
+use crate::{FinalPost, JobPost};
+
+pub async fn fetch_jobs(search_params: &str) -> eyre::Result<Vec<JobPost>> {
+    let byte_data = req_bytes(search_params).await?;
+    parse_xml(&byte_data[..])
+}
+
+pub fn price_label(job: &JobPost) -> String {
+    let budget = job.detail.get("Budget");
+    let hourly = job.detail.get("Hourly Range");
+
+    match (budget, hourly) {
+        (Some(b), None) => format!("Budget : {}", b),
+        (None, Some(h)) => format!("Hourly Range : {}", h),
+        (_, _) => "Unknown".to_string(),
+    }
+}
 
 pub fn populate_data(byte_data: Bytes) -> eyre::Result<Vec<FinalPost>> {
     let result_data = parse_xml(&byte_data[..])?;
@@ -14,20 +35,7 @@ pub fn populate_data(byte_data: Bytes) -> eyre::Result<Vec<FinalPost>> {
     let list_job: Vec<FinalPost> = result_data
         .into_iter()
         .map(|j| {
-            let budget = j.detail.get("Budget");
-            let hourly = j.detail.get("Hourly Range");
-
-            let mut price = "Unknown".to_string();
-
-            match (budget, hourly) {
-                (Some(b), None) => {
-                    price = format!("Budget : {}", b);
-                }
-                (None, Some(h)) => {
-                    price = format!("Hourly Range : {}", h);
-                }
-                (_, _) => (),
-            }
+            let price = price_label(&j);
 
             let desc = j
                 .detail
@@ -35,13 +43,12 @@ pub fn populate_data(byte_data: Bytes) -> eyre::Result<Vec<FinalPost>> {
                 .unwrap_or(&"No Description".to_owned())
                 .clone();
 
-            let response_json = FinalPost {
+            FinalPost {
                 title: j.title.clone(),
                 link: j.link.clone(),
                 detail: desc.to_owned(),
                 price,
-            };
-            response_json
+            }
         })
         .collect();
 
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::JobPost;
+
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(48 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterRules {
+    #[serde(with = "humantime_serde")]
+    pub max_age: Option<Duration>,
+    /// Only show jobs from clients in these countries, when not empty
+    pub allow_countries: Vec<String>,
+    /// Whether `allow_countries` lets through jobs that name no country, like
+    /// those of most non-Upwork feeds
+    pub allow_unknown_country: bool,
+    pub deny_countries: Vec<String>,
+    /// Where the team is based, to flag jobs whose location requirement excludes us
+    pub team_countries: Vec<String>,
+}
+
+impl Default for FilterRules {
+    fn default() -> Self {
+        Self {
+            max_age: Some(DEFAULT_MAX_AGE),
+            allow_countries: Vec::new(),
+            allow_unknown_country: true,
+            deny_countries: Vec::new(),
+            team_countries: Vec::new(),
+        }
+    }
+}
+
+impl FilterRules {
+    pub fn is_expired(&self, job: &JobPost, now: i64) -> bool {
+        match self.max_age {
+            Some(max_age) => now - job.posted_timestamp > max_age.as_secs() as i64,
+            None => false,
+        }
+    }
+
+    pub fn country_allowed(&self, job: &JobPost) -> bool {
+        let listed = |countries: &[String]| {
+            job.country.as_ref().is_some_and(|country| {
+                countries
+                    .iter()
+                    .any(|listed| listed.eq_ignore_ascii_case(country))
+            })
+        };
+
+        let allowed = self.allow_countries.is_empty()
+            || listed(&self.allow_countries)
+            || (job.country.is_none() && self.allow_unknown_country);
+
+        allowed && !listed(&self.deny_countries)
+    }
+
+    /// True when the job is limited to locations none of `team_countries` are
+    /// in. Nothing is flagged until `team_countries` is configured.
+    pub fn is_restricted(&self, job: &JobPost) -> bool {
+        match &job.location_requirement {
+            Some(requirement) if !self.team_countries.is_empty() => {
+                !requirement.allows_any(&self.team_countries)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn matches(&self, job: &JobPost, now: i64) -> bool {
+        !self.is_expired(job, now) && self.country_allowed(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::location::LocationRequirement;
+
+    fn job_posted_at(timestamp: i64) -> JobPost {
+        JobPost {
+            title: "Title".to_string(),
+            link: "https://linktest.com".to_string(),
+            category: "web_design".to_string(),
+            posted_timestamp: timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_age_window() {
+        let now = 1693880353;
+        let rules = FilterRules {
+            max_age: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+
+        assert!(rules.matches(&job_posted_at(now - 60), now));
+        assert!(rules.matches(&job_posted_at(now - 3600), now));
+        assert!(rules.is_expired(&job_posted_at(now - 3601), now));
+    }
+
+    #[test]
+    fn test_no_age_window() {
+        let rules = FilterRules {
+            max_age: None,
+            ..Default::default()
+        };
+
+        assert!(rules.matches(&job_posted_at(0), 1693880353));
+    }
+
+    #[test]
+    fn test_country_lists() {
+        let rules: FilterRules = toml::from_str(
+            r#"
+            allow_countries = ["United States", "canada"]
+            deny_countries = ["Canada"]
+            "#,
+        )
+        .unwrap();
+        let from = |country: Option<&str>| JobPost {
+            country: country.map(str::to_owned),
+            ..job_posted_at(0)
+        };
+
+        assert!(rules.country_allowed(&from(Some("united states"))));
+        assert!(!rules.country_allowed(&from(Some("Canada"))));
+        assert!(!rules.country_allowed(&from(Some("India"))));
+        assert!(rules.country_allowed(&from(None)));
+        assert!(FilterRules::default().country_allowed(&from(None)));
+
+        let strict = FilterRules {
+            allow_unknown_country: false,
+            ..rules
+        };
+        assert!(!strict.country_allowed(&from(None)));
+        assert!(strict.country_allowed(&from(Some("United States"))));
+    }
+
+    #[test]
+    fn test_location_restriction() {
+        let us_only = JobPost {
+            location_requirement: Some(LocationRequirement::parse(
+                "Only freelancers located in the United States may apply.",
+            )),
+            ..job_posted_at(0)
+        };
+        let mut rules = FilterRules::default();
+
+        assert!(!rules.is_restricted(&us_only));
+
+        rules.team_countries = vec!["Germany".to_string()];
+        assert!(rules.is_restricted(&us_only));
+        assert!(!rules.is_restricted(&job_posted_at(0)));
+
+        rules.team_countries.push("United States".to_string());
+        assert!(!rules.is_restricted(&us_only));
+    }
+}
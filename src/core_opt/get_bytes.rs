@@ -1,20 +1,26 @@
 use bytes::Bytes;
 use color_eyre::eyre::Result;
-use reqwest::Client;
-use std::collections::HashMap;
+use reqwest::{Client, Url};
+use tracing::instrument;
 
+#[instrument]
 pub async fn req_bytes(search_params: &str) -> Result<Bytes> {
     let uri = "https://www.upwork.com/ab/feed/jobs/rss";
-    let query_data: HashMap<&str, &str> =
-        HashMap::from([("sort", "recency"), ("q", search_params)]);
+    let url = Url::parse_with_params(uri, [("sort", "recency"), ("q", search_params)])?;
 
+    req_url(url.as_str()).await
+}
+
+#[instrument]
+pub async fn req_url(url: &str) -> Result<Bytes> {
     let response_byte = Client::new()
-        .get(uri)
-        .query(&query_data)
+        .get(url)
         .send()
         .await?
+        .error_for_status()?
         .bytes()
         .await?;
+    tracing::debug!(bytes = response_byte.len(), "fetched feed");
 
     Ok(response_byte)
 }
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocationRequirement {
+    /// Countries or regions named in the requirement, empty when the text
+    /// did not follow the usual "Only freelancers located in ..." wording.
+    pub locations: Vec<String>,
+    pub text: String,
+}
+
+impl LocationRequirement {
+    /// Only the last " and " of the list separates locations, so names like
+    /// "Trinidad and Tobago" survive anywhere but in the final position.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let mut parts: Vec<&str> = listed(text).split(',').collect();
+        if let Some((last, rest)) = parts
+            .pop()
+            .map(|last| last.rsplit_once(" and ").unwrap_or((last, "")))
+        {
+            parts.extend([last, rest]);
+        }
+
+        let locations = parts
+            .into_iter()
+            .map(|location| {
+                let location = location.trim();
+                location.strip_prefix("the ").unwrap_or(location).trim()
+            })
+            .filter(|location| !location.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        Self {
+            locations,
+            text: text.to_owned(),
+        }
+    }
+
+    /// Whether a freelancer in one of `countries` may apply. Unparsed
+    /// requirements never allow anyone, so they always get flagged.
+    pub fn allows_any(&self, countries: &[String]) -> bool {
+        countries.iter().any(|country| {
+            self.locations
+                .iter()
+                .any(|location| country.eq_ignore_ascii_case(location))
+                || self.names(country)
+        })
+    }
+
+    /// Whether `country` is one whole entry of the list, which catches the
+    /// names with an " and " of their own that were split by `parse`.
+    fn names(&self, country: &str) -> bool {
+        let list = listed(&self.text).to_ascii_lowercase();
+        let country = country.trim().to_ascii_lowercase();
+        if country.is_empty() {
+            return false;
+        }
+
+        list.match_indices(&country).any(|(at, _)| {
+            let before = list[..at].trim_end();
+            let before = before
+                .strip_suffix("the")
+                .filter(|rest| rest.is_empty() || rest.ends_with(' '))
+                .map_or(before, str::trim_end);
+            let after = list[at + country.len()..].trim_start();
+
+            (before.is_empty() || before.ends_with(',') || before.ends_with(" and"))
+                && (after.is_empty() || after.starts_with(',') || after.starts_with("and "))
+        })
+    }
+}
+
+/// The list of locations in an "Only freelancers located in ... may apply"
+/// requirement, empty for any other wording.
+fn listed(text: &str) -> &str {
+    text.trim()
+        .strip_prefix("Only freelancers located in")
+        .and_then(|rest| rest.trim_end_matches('.').strip_suffix("may apply"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location_requirement() {
+        let requirement =
+            LocationRequirement::parse("Only freelancers located in the United States may apply.");
+        assert_eq!(requirement.locations, vec!["United States"]);
+
+        let requirement = LocationRequirement::parse(
+            "Only freelancers located in Canada, the United Kingdom and Ireland may apply.",
+        );
+        assert_eq!(
+            requirement.locations,
+            vec!["Canada", "United Kingdom", "Ireland"]
+        );
+        assert!(requirement.allows_any(&["ireland".to_string()]));
+        assert!(!requirement.allows_any(&["Germany".to_string()]));
+
+        let requirement = LocationRequirement::parse(
+            "Only freelancers located in Bosnia and Herzegovina may apply.",
+        );
+        assert!(requirement.allows_any(&["Bosnia and Herzegovina".to_string()]));
+        assert!(!requirement.allows_any(&["Serbia".to_string()]));
+
+        let requirement = LocationRequirement::parse(
+            "Only freelancers located in Trinidad and Tobago, Jamaica and Antigua and Barbuda may apply.",
+        );
+        assert_eq!(
+            requirement.locations,
+            vec!["Trinidad and Tobago", "Jamaica and Antigua", "Barbuda"]
+        );
+        assert!(requirement.allows_any(&["trinidad and tobago".to_string()]));
+        assert!(requirement.allows_any(&["Antigua and Barbuda".to_string()]));
+        assert!(requirement.allows_any(&["Jamaica".to_string()]));
+        assert!(!requirement.allows_any(&["Guinea".to_string()]));
+
+        let requirement = LocationRequirement::parse("Americas only");
+        assert!(requirement.locations.is_empty());
+        assert_eq!(requirement.text, "Americas only");
+    }
+}
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
-use crate::JobPost;
-use color_eyre::eyre::{self, eyre};
-use scraper::{Html, Selector};
+use crate::{core_opt::location::LocationRequirement, JobPost};
+use color_eyre::eyre::{self, bail, eyre};
+use quick_xml::escape::resolve_html5_entity;
+use tracing::instrument;
 
+#[instrument(level = "trace", skip_all, fields(link = %link_raw))]
 pub fn mapped_detail(
     posted: String,
     timestamp: i64,
@@ -11,7 +13,9 @@ pub fn mapped_detail(
     link_raw: String,
     desc: String,
 ) -> eyre::Result<JobPost> {
-    let links: Vec<_> = link_raw.split('?').collect();
+    let link = link_raw
+        .split_once('?')
+        .map_or(link_raw.as_str(), |(link, _)| link);
 
     let details = get_detail(&desc)?;
 
@@ -19,77 +23,199 @@ pub fn mapped_detail(
         .get("Category")
         .ok_or_else(|| eyre!("category not found"))?;
     let result_string = category.to_lowercase().replace(' ', "_");
+    let country = details.get("Country").cloned();
+    let location_requirement = details
+        .get("Location Requirement")
+        .map(|text| LocationRequirement::parse(text));
 
     let job_post = JobPost {
         title: title_raw,
-        link: links[0].to_owned(),
+        link: link.to_owned(),
         detail: details,
         posted_on: posted,
         posted_timestamp: timestamp,
         category: result_string,
+        country,
+        location_requirement,
+        id: None,
     };
 
     Ok(job_post)
 }
 
+/// Reads the `<b>Key</b>: value` pairs of an item description, plus the text
+/// before the first of them as `details`. Scans the HTML in place rather than
+/// building a DOM, with the same results as an HTML parser for these feeds.
 pub fn get_detail(description: &str) -> eyre::Result<HashMap<String, String>> {
-    let doc = Html::parse_document(description);
+    let mut mapped = HashMap::new();
+    let mut details = String::new();
+    let mut rest = description.trim_start();
+    let mut seen_bold = false;
 
-    let selector = Selector::parse("b").unwrap();
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            if !seen_bold {
+                decode_text(rest, &mut details);
+            }
+            break;
+        };
 
-    let elements = doc.select(&selector);
-    let mut mapped = HashMap::new();
+        if !seen_bold {
+            decode_text(&rest[..tag_start], &mut details);
+        }
+        rest = &rest[tag_start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
 
-    for element in elements {
-        let key = element.inner_html();
+        let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        let tag = &rest[..tag_end];
+        rest = &rest[tag_end..];
 
-        if key == *"Posted On".to_owned() {
+        if !is_start_tag(tag, "b") {
             continue;
         }
+        seen_bold = true;
 
-        let value: String = element
-            .next_sibling()
-            .ok_or_else(|| eyre!("html parse: dont have next sibling"))?
-            .value()
-            .as_text()
-            .ok_or_else(|| eyre!("html parse: are not a text"))?
-            .to_string();
+        let (key, after) =
+            split_close_tag(rest, "b").ok_or_else(|| eyre!("html parse: unclosed <b>"))?;
+        rest = after;
 
+        if key == "Posted On" {
+            continue;
+        }
+
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        if text_end == 0 {
+            if rest.is_empty() {
+                bail!("html parse: dont have next sibling");
+            }
+            bail!("html parse: are not a text");
+        }
+
+        let mut value = String::new();
+        decode_text(&rest[..text_end], &mut value);
         let value: Vec<&str> = value.trim_start_matches(':').split_whitespace().collect();
-        let value = value.join(" ");
 
-        mapped.insert(key, value);
+        mapped.insert(key.to_owned(), value.join(" "));
     }
 
-    let nodes = doc.tree.nodes();
+    mapped.insert("details".to_string(), details);
 
-    let mut details = String::new();
+    Ok(mapped)
+}
 
-    for node in nodes {
-        let val = node.value();
-
-        if val.is_element() {
-            match val.as_element() {
-                Some(val_el) => {
-                    if val_el.name() == "b" {
-                        break;
-                    }
-                }
-                None => (),
-            }
+/// The text of an HTML description with the markup dropped, for feeds
+/// without Upwork's labelled fields. Line and paragraph breaks are kept.
+pub fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(tag_start) = rest.find('<') {
+        decode_text(&rest[..tag_start], &mut text);
+        rest = &rest[tag_start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+
+        let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        let tag = &rest[..tag_end];
+        rest = &rest[tag_end..];
+
+        if is_start_tag(tag, "br") || is_start_tag(tag, "p") || is_start_tag(tag, "li") {
+            text.push('\n');
         }
+    }
+    decode_text(rest, &mut text);
+
+    text.lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
+
+fn is_start_tag(tag: &str, name: &str) -> bool {
+    tag.strip_prefix('<')
+        .and_then(|tag| {
+            tag.get(..name.len())
+                .filter(|found| found.eq_ignore_ascii_case(name))
+        })
+        .is_some_and(|_| {
+            let after = &tag[1 + name.len()..];
+            after.starts_with(['>', '/', ' ', '\t', '\n', '\r'])
+        })
+}
+
+/// Splits `text` around the first `</name>`, matching the tag name in any
+/// case as `is_start_tag` does.
+fn split_close_tag<'a>(text: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    text.match_indices("</").find_map(|(start, _)| {
+        let after = &text[start + 2..];
+        after
+            .get(..name.len())
+            .filter(|found| found.eq_ignore_ascii_case(name))?;
+        let rest = after[name.len()..].trim_start().strip_prefix('>')?;
+
+        Some((&text[..start], rest))
+    })
+}
+
+/// Appends HTML text with character references decoded and line endings
+/// normalised, leaving unknown `&...;` sequences as they are.
+fn decode_text(text: &str, out: &mut String) {
+    let mut rest = text;
 
-        if val.is_text() {
-            match val.as_text() {
-                Some(text) => details.push_str(&text.to_string()),
-                None => (),
+    while let Some(amp) = rest.find('&') {
+        push_normalised(&rest[..amp], out);
+        rest = &rest[amp..];
+
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|end| *end <= MAX_ENTITY_LEN)
+            .and_then(|end| Some((decode_entity(&rest[1..=end])?, end + 2)));
+
+        match decoded {
+            Some((decoded, len)) => {
+                out.push_str(&decoded);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
             }
         }
     }
 
-    mapped.insert("details".to_string(), details);
+    push_normalised(rest, out);
+}
 
-    Ok(mapped)
+const MAX_ENTITY_LEN: usize = 32;
+
+fn decode_entity(entity: &str) -> Option<Cow<'static, str>> {
+    let Some(number) = entity.strip_prefix('#') else {
+        return resolve_html5_entity(entity).map(Cow::Borrowed);
+    };
+
+    let code = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => number.parse().ok()?,
+    };
+
+    char::from_u32(code).map(|c| Cow::Owned(c.to_string()))
+}
+
+fn push_normalised(text: &str, out: &mut String) {
+    if text.contains('\r') {
+        out.push_str(&text.replace("\r\n", "\n").replace('\r', "\n"));
+    } else {
+        out.push_str(text);
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +257,7 @@ mod tests {
                 .to_string(),
         );
         expected1.insert("Country".to_string(), "United States".to_string());
+        expected1.insert("details".to_string(), "Picture needs to be designed for the HERO page. Background needs to be changed and some design adjustments".to_string());
 
         let job_post = JobPost {
             title: "Title 01".to_string(),
@@ -139,6 +266,8 @@ mod tests {
             posted_on: posted.clone(),
             posted_timestamp: timestamp,
             category,
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mapped_detail =
@@ -168,6 +297,11 @@ mod tests {
             .to_string(),
     );
         expected2.insert("Country".to_string(), "United States".to_string());
+        expected2.insert(
+            "details".to_string(),
+            "We need a new design for our company website. Must be modern and user-friendly."
+                .to_string(),
+        );
 
         let job_post = JobPost {
             title: title_test.clone(),
@@ -176,6 +310,8 @@ mod tests {
             posted_on: posted.clone(),
             posted_timestamp: timestamp,
             category,
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mapped_detail = mapped_detail(
@@ -210,6 +346,10 @@ mod tests {
             "Logo Design, Graphic Design, Branding".to_string(),
         );
         expected3.insert("Country".to_string(), "United States".to_string());
+        expected3.insert(
+            "details".to_string(),
+            "We need a new logo for our company. Must be modern and eye-catching.".to_string(),
+        );
 
         let job_post = JobPost {
             title: title_test.clone(),
@@ -218,6 +358,8 @@ mod tests {
             posted_on: posted.clone(),
             posted_timestamp: timestamp,
             category,
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mapped_detail = mapped_detail(
@@ -252,6 +394,7 @@ mod tests {
             "Content Writing, Blog Writing, Tech Writing".to_string(),
         );
         expected4.insert("Country".to_string(), "United States".to_string());
+        expected4.insert("details".to_string(), "We need a content writer for our company blog. Must have experience in the tech industry.".to_string());
 
         let job_post = JobPost {
             title: title_test.clone(),
@@ -260,6 +403,8 @@ mod tests {
             posted_on: posted.clone(),
             posted_timestamp: timestamp,
             category,
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mapped_detail = mapped_detail(
@@ -291,6 +436,7 @@ mod tests {
             "HTML, CSS, JavaScript, Web Development".to_string(),
         );
         expected5.insert("Country".to_string(), "United States".to_string());
+        expected5.insert("details".to_string(), "We need a web developer for our company website. Must have experience with modern web technologies.".to_string());
 
         let job_post = JobPost {
             title: title_test.clone(),
@@ -299,6 +445,8 @@ mod tests {
             posted_on: posted.clone(),
             posted_timestamp: timestamp,
             category,
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mapped_detail = mapped_detail(
@@ -313,6 +461,54 @@ mod tests {
         assert_eq!(job_post, mapped_detail);
     }
 
+    #[test]
+    fn test_location_requirement_is_typed() -> eyre::Result<()> {
+        let description = "Need a US based accountant.<br /><br /><b>Budget</b>: $200\n<br /><b>Posted On</b>: April 29, 2024 09:00 UTC<br /><b>Category</b>: Accounting<br /><b>Location Requirement</b>: Only freelancers located in the United States may apply.\n<br /><b>Country</b>: United States\n<br /><a href=\"https://www.upwork.com/jobs/Accountant_%7E0136d9648f43b2532b?source=rss\">click to apply</a>\n";
+
+        let job = mapped_detail(
+            "Mon, 29 Apr 2024 09:00:00 +0000".to_string(),
+            1714381200,
+            "Accountant".to_string(),
+            "https://linktest6.com".to_string(),
+            description.to_string(),
+        )?;
+
+        assert_eq!(job.country.as_deref(), Some("United States"));
+        assert_eq!(
+            job.location_requirement
+                .map(|requirement| requirement.locations),
+            Some(vec!["United States".to_string()])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_detail_tag_case_and_posted_on() -> eyre::Result<()> {
+        let detail = get_detail(
+            "Logo work<br /><B>Budget</B>: $80<br /><b>Posted On</b><br /><b>Category</b>: Logo Design</B >",
+        )?;
+
+        assert_eq!(detail["details"], "Logo work");
+        assert_eq!(detail["Budget"], "$80");
+        assert_eq!(detail["Category"], "Logo Design");
+        assert!(!detail.contains_key("Posted On"));
+        assert!(get_detail("<b>Budget</b><br />").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_text() {
+        let html = "<p>Build a <b>Rust</b> CLI &amp; API.</p>\n<ul><li>Tokio</li><li>Axum</li></ul><!-- tracking --><br/>Remote&nbsp;only";
+
+        assert_eq!(
+            html_text(html),
+            "Build a Rust CLI & API.\n\nTokio\nAxum\nRemote\u{a0}only"
+        );
+        assert_eq!(html_text("plain text"), "plain text");
+    }
+
     // #[test]
     // fn test_error_get_detail() {
     //     let title_test = "Title 05".to_string();
@@ -0,0 +1,265 @@
+use std::io::BufRead;
+
+use color_eyre::eyre;
+use quick_xml::{
+    escape::resolve_xml_entity,
+    events::{BytesRef, BytesStart, Event},
+    Reader, XmlVersion,
+};
+
+use crate::{
+    core_opt::source::{Source, Upwork},
+    JobPost,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Link,
+    Description,
+    Published,
+    Updated,
+    Category,
+}
+
+/// An RSS `<item>` or Atom `<entry>` as written in the feed, before a
+/// [`Source`] maps it onto a `JobPost`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedEntry {
+    pub title: String,
+    pub link: String,
+    /// `<description>`, or whichever of `<summary>` and `<content>` comes first
+    pub description: Option<String>,
+    /// `<pubDate>` or `<published>`
+    pub published: String,
+    pub updated: String,
+    pub categories: Vec<String>,
+}
+
+/// Yields the `JobPost` of each RSS `<item>` or Atom `<entry>` as it is read,
+/// so archives of many feeds, even concatenated into one stream, never sit in
+/// memory whole.
+pub struct JobStream<R, S = Upwork> {
+    reader: Reader<R>,
+    source: S,
+    buf: Vec<u8>,
+    item: Option<FeedEntry>,
+    field: Option<Field>,
+    failed: bool,
+}
+
+impl<R: BufRead> JobStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_source(reader, Upwork)
+    }
+}
+
+impl<R: BufRead, S: Source> JobStream<R, S> {
+    pub fn with_source(reader: R, source: S) -> Self {
+        Self {
+            reader: Reader::from_reader(reader),
+            source,
+            buf: Vec::new(),
+            item: None,
+            field: None,
+            failed: false,
+        }
+    }
+}
+
+impl<R: BufRead, S: Source> Iterator for JobStream<R, S> {
+    type Item = eyre::Result<JobPost>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        loop {
+            self.buf.clear();
+            let event = match self.reader.read_event_into(&mut self.buf) {
+                Ok(event) => event,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err.into()));
+                }
+            };
+
+            match event {
+                Event::Start(start) => match start_field(&mut self.item, &start) {
+                    Ok(field) => self.field = field,
+                    Err(err) => return Some(Err(err)),
+                },
+                Event::Empty(start) => {
+                    self.field = None;
+                    if let Err(err) = start_field(&mut self.item, &start) {
+                        return Some(Err(err));
+                    }
+                }
+                Event::End(end) => {
+                    self.field = None;
+
+                    if matches!(end.local_name().as_ref(), b"item" | b"entry") {
+                        if let Some(job) = self.item.take().and_then(|item| self.source.job(item)) {
+                            return Some(job);
+                        }
+                    }
+                }
+                Event::Text(text) => match text.decode() {
+                    Ok(text) => push_text(&mut self.item, self.field, &text),
+                    Err(err) => return Some(Err(err.into())),
+                },
+                Event::CData(data) => match data.decode() {
+                    Ok(data) => push_text(&mut self.item, self.field, &data),
+                    Err(err) => return Some(Err(err.into())),
+                },
+                Event::GeneralRef(reference) => match resolve_reference(&reference) {
+                    Ok(text) => push_text(&mut self.item, self.field, &text),
+                    Err(err) => return Some(Err(err)),
+                },
+                Event::Eof => return None,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Starts a new entry at `<item>` or `<entry>`, and otherwise tells which
+/// field the text of `start` belongs to. Atom keeps links and categories in
+/// attributes, which are read here instead.
+fn start_field(item: &mut Option<FeedEntry>, start: &BytesStart) -> eyre::Result<Option<Field>> {
+    let name = start.local_name();
+    if matches!(name.as_ref(), b"item" | b"entry") {
+        *item = Some(FeedEntry::default());
+        return Ok(None);
+    }
+
+    let Some(item) = item else {
+        return Ok(None);
+    };
+
+    let field = match name.as_ref() {
+        b"title" => Some(Field::Title),
+        b"link" => match attribute(start, "href")? {
+            Some(href) => {
+                let rel = attribute(start, "rel")?;
+                if item.link.is_empty() && rel.is_none_or(|rel| rel == "alternate") {
+                    item.link = href;
+                }
+                None
+            }
+            None => Some(Field::Link),
+        },
+        b"description" | b"summary" | b"content" if item.description.is_none() => {
+            Some(Field::Description)
+        }
+        b"pubDate" | b"published" => Some(Field::Published),
+        b"updated" => Some(Field::Updated),
+        b"category" => match attribute(start, "term")? {
+            Some(term) => {
+                item.categories.push(term);
+                None
+            }
+            None => {
+                item.categories.push(String::new());
+                Some(Field::Category)
+            }
+        },
+        _ => None,
+    };
+
+    Ok(field)
+}
+
+fn attribute(start: &BytesStart, name: &str) -> eyre::Result<Option<String>> {
+    let Some(attribute) = start.try_get_attribute(name)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        attribute
+            .normalized_value(XmlVersion::Implicit1_0)?
+            .into_owned(),
+    ))
+}
+
+fn push_text(item: &mut Option<FeedEntry>, field: Option<Field>, text: &str) {
+    let (Some(item), Some(field)) = (item, field) else {
+        return;
+    };
+
+    match field {
+        Field::Title => item.title.push_str(text),
+        Field::Link => item.link.push_str(text),
+        Field::Description => item.description.get_or_insert_default().push_str(text),
+        Field::Published => item.published.push_str(text),
+        Field::Updated => item.updated.push_str(text),
+        Field::Category => {
+            if let Some(category) = item.categories.last_mut() {
+                category.push_str(text);
+            }
+        }
+    }
+}
+
+fn resolve_reference(reference: &BytesRef) -> eyre::Result<String> {
+    if let Some(c) = reference.resolve_char_ref()? {
+        return Ok(c.to_string());
+    }
+
+    let name = reference.decode()?;
+    let text =
+        resolve_xml_entity(&name).ok_or_else(|| eyre::eyre!("unknown entity `&{};`", name))?;
+
+    Ok(text.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_streams_concatenated_feeds() -> eyre::Result<()> {
+        let feed = fs::read("job.xml")?;
+        let single: Vec<JobPost> = JobStream::new(&feed[..]).collect::<eyre::Result<_>>()?;
+
+        let archive = [&feed[..], b"\n", &feed[..]].concat();
+        let mut jobs = JobStream::new(&archive[..]);
+
+        let first = jobs.next().transpose()?;
+        assert_eq!(first.as_ref(), single.first());
+        assert_eq!(jobs.count(), single.len() * 2 - 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_entities_and_missing_descriptions() -> eyre::Result<()> {
+        let feed = r#"<?xml version="1.0"?>
+            <rss><channel>
+                <title>Channel &amp; title</title>
+                <item><title>Not a job</title></item>
+                <item>
+                    <title>Logo &amp; brand &#8211; Upwork</title>
+                    <link>https://www.upwork.com/jobs/Logo_%7E01?source=rss&amp;x=1</link>
+                    <description>Fresh look &amp;lt;3&lt;br /&gt;&lt;b&gt;Category&lt;/b&gt;: Logo Design&lt;br /&gt;</description>
+                    <pubDate>Mon, 29 Apr 2024 09:00:00 +0000</pubDate>
+                </item>
+            </channel></rss>"#;
+
+        let jobs: Vec<JobPost> = JobStream::new(feed.as_bytes()).collect::<eyre::Result<_>>()?;
+
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].title, "Logo & brand – Upwork");
+        assert_eq!(jobs[0].link, "https://www.upwork.com/jobs/Logo_%7E01");
+        assert_eq!(jobs[0].detail["details"], "Fresh look <3");
+        assert_eq!(jobs[0].category, "logo_design");
+
+        let broken = JobStream::new(&b"<rss><item><title>x</titel>"[..]).collect::<Vec<_>>();
+        assert_eq!(broken.len(), 1);
+        assert!(broken[0].is_err());
+
+        Ok(())
+    }
+}
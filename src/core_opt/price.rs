@@ -0,0 +1,320 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use color_eyre::eyre::{self, bail, eyre};
+use serde::Deserialize;
+
+use crate::JobPost;
+
+const SYMBOLS: [(&str, &str); 8] = [
+    ("US$", "USD"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₱", "PHP"),
+    ("₦", "NGN"),
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CurrencyConfig {
+    pub preferred: String,
+    /// Units of `preferred` one unit of each listed currency is worth
+    pub rates: HashMap<String, f64>,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            preferred: "USD".to_string(),
+            rates: HashMap::new(),
+        }
+    }
+}
+
+impl CurrencyConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        if !is_code(&self.preferred) {
+            bail!("preferred: `{}` is not a currency code", self.preferred);
+        }
+
+        for (code, rate) in &self.rates {
+            if !is_code(code) {
+                bail!("rates: `{}` is not a currency code", code);
+            }
+            if !rate.is_finite() || *rate <= 0.0 {
+                bail!("rates.{}: must be a positive number", code);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn rate(&self, currency: &str) -> Option<f64> {
+        if currency.eq_ignore_ascii_case(&self.preferred) {
+            return Some(1.0);
+        }
+
+        self.rates
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(currency))
+            .map(|(_, rate)| *rate)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Amount {
+    pub value: f64,
+    /// ISO 4217 code, e.g. `USD`
+    pub currency: String,
+}
+
+impl Amount {
+    pub fn convert(&self, config: &CurrencyConfig) -> Option<Amount> {
+        let rate = config.rate(&self.currency)?;
+
+        Some(Amount {
+            value: self.value * rate,
+            currency: config.preferred.to_uppercase(),
+        })
+    }
+}
+
+impl FromStr for Amount {
+    type Err = eyre::Report;
+
+    /// Parses amounts like `$80`, `€1,200.50`, `USD 80` or `80 EUR`.
+    fn from_str(raw: &str) -> eyre::Result<Self> {
+        let text = raw.trim();
+
+        let (currency, number) = SYMBOLS
+            .iter()
+            .find_map(|(symbol, code)| Some((*code, text.strip_prefix(symbol)?)))
+            .or_else(|| {
+                let (code, number) = text.split_at_checked(3)?;
+                is_code(code).then_some((code, number))
+            })
+            .or_else(|| {
+                let (number, code) = text.split_at_checked(text.len().checked_sub(3)?)?;
+                is_code(code).then_some((code, number))
+            })
+            .ok_or_else(|| eyre!("no currency in amount `{}`", raw))?;
+
+        let value = parse_number(number.trim()).ok_or_else(|| eyre!("invalid amount `{}`", raw))?;
+
+        Ok(Self {
+            value,
+            currency: currency.to_uppercase(),
+        })
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = SYMBOLS
+            .iter()
+            .skip(1)
+            .find(|(_, code)| *code == self.currency)
+            .map(|(symbol, _)| symbol.to_string())
+            .unwrap_or_else(|| format!("{} ", self.currency));
+
+        if self.value.fract() == 0.0 {
+            write!(f, "{}{}", symbol, self.value)
+        } else {
+            write!(f, "{}{:.2}", symbol, self.value)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Price {
+    Fixed(Amount),
+    Hourly { min: Amount, max: Amount },
+}
+
+impl Price {
+    /// The budget, or the hourly range when there is no budget that parses.
+    pub fn from_job(job: &JobPost) -> Option<Self> {
+        let budget = job
+            .detail
+            .get("Budget")
+            .and_then(|budget| budget.parse().ok());
+        if let Some(budget) = budget {
+            return Some(Price::Fixed(budget));
+        }
+
+        let hourly = job.detail.get("Hourly Range")?;
+        let (min, max) = match hourly.split_once(['-', '–']) {
+            Some((min, max)) => (min.parse().ok()?, max.parse().ok()?),
+            None => {
+                let rate: Amount = hourly.parse().ok()?;
+                (rate.clone(), rate)
+            }
+        };
+
+        Some(Price::Hourly { min, max })
+    }
+
+    pub fn convert(&self, config: &CurrencyConfig) -> Option<Price> {
+        match self {
+            Price::Fixed(amount) => amount.convert(config).map(Price::Fixed),
+            Price::Hourly { min, max } => Some(Price::Hourly {
+                min: min.convert(config)?,
+                max: max.convert(config)?,
+            }),
+        }
+    }
+
+    /// Renders in the preferred currency with the original alongside, or just
+    /// the original when it is already preferred or has no configured rate.
+    pub fn render(&self, config: &CurrencyConfig) -> String {
+        match self.convert(config) {
+            Some(converted) if converted != *self => format!("{} ({})", converted, self),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Price::Fixed(amount) => write!(f, "{}", amount),
+            Price::Hourly { min, max } if min == max => write!(f, "{}/h", min),
+            Price::Hourly { min, max } => write!(f, "{}-{}/h", min, max),
+        }
+    }
+}
+
+/// Reads `1,200.50` as well as `1.200,50`: when both separators appear the
+/// last one is the decimal point. A lone comma is a decimal point only when
+/// fewer than three digits follow it, as in `12,50`.
+fn parse_number(number: &str) -> Option<f64> {
+    if !number.starts_with(|c: char| c.is_ascii_digit())
+        || !number
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+
+    let decimal = match (number.rfind(','), number.rfind('.')) {
+        (Some(comma), Some(dot)) => Some(comma.max(dot)),
+        (Some(comma), None) if number.matches(',').count() == 1 && number.len() - comma < 4 => {
+            Some(comma)
+        }
+        (None, Some(dot)) if number.matches('.').count() == 1 => Some(dot),
+        _ => None,
+    };
+
+    let mut plain = String::with_capacity(number.len());
+    for (i, c) in number.char_indices() {
+        match c {
+            _ if Some(i) == decimal => plain.push('.'),
+            ',' | '.' => (),
+            _ => plain.push(c),
+        }
+    }
+
+    plain.parse().ok().filter(|value: &f64| value.is_finite())
+}
+
+fn is_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(value: f64, currency: &str) -> Amount {
+        Amount {
+            value,
+            currency: currency.to_string(),
+        }
+    }
+
+    fn job_with(key: &str, value: &str) -> JobPost {
+        JobPost {
+            detail: HashMap::from([(key.to_string(), value.to_string())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_amounts() -> eyre::Result<()> {
+        assert_eq!("$80".parse::<Amount>()?, amount(80.0, "USD"));
+        assert_eq!("€1,200.50".parse::<Amount>()?, amount(1200.5, "EUR"));
+        assert_eq!("US$ 15".parse::<Amount>()?, amount(15.0, "USD"));
+        assert_eq!("GBP 40".parse::<Amount>()?, amount(40.0, "GBP"));
+        assert_eq!("500 inr".parse::<Amount>()?, amount(500.0, "INR"));
+        assert!("80".parse::<Amount>().is_err());
+        assert!("$eighty".parse::<Amount>().is_err());
+        assert_eq!("€1.200,50".parse::<Amount>()?, amount(1200.5, "EUR"));
+        assert_eq!("€12,50".parse::<Amount>()?, amount(12.5, "EUR"));
+        assert_eq!("$1,000,000".parse::<Amount>()?, amount(1_000_000.0, "USD"));
+        assert_eq!("€1.000.000".parse::<Amount>()?, amount(1_000_000.0, "EUR"));
+        assert!("$NaN".parse::<Amount>().is_err());
+        assert!("$inf".parse::<Amount>().is_err());
+        assert!("$1e999".parse::<Amount>().is_err());
+        assert!("$-5".parse::<Amount>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_price_from_job() {
+        assert_eq!(
+            Price::from_job(&job_with("Hourly Range", "$10.00-$20.00")),
+            Some(Price::Hourly {
+                min: amount(10.0, "USD"),
+                max: amount(20.0, "USD")
+            })
+        );
+        assert_eq!(
+            Price::from_job(&job_with("Budget", "£80")),
+            Some(Price::Fixed(amount(80.0, "GBP")))
+        );
+        assert_eq!(Price::from_job(&job_with("Skills", "Rust")), None);
+
+        let mut both = job_with("Budget", "TBD");
+        both.detail
+            .insert("Hourly Range".to_string(), "$15.00-$30.00".to_string());
+        assert_eq!(
+            Price::from_job(&both),
+            Some(Price::Hourly {
+                min: amount(15.0, "USD"),
+                max: amount(30.0, "USD")
+            })
+        );
+        assert_eq!(Price::from_job(&job_with("Budget", "TBD")), None);
+    }
+
+    #[test]
+    fn test_render_in_preferred_currency() {
+        let config: CurrencyConfig =
+            toml::from_str("preferred = \"EUR\"\nrates = { USD = 0.5 }").unwrap();
+
+        let hourly = Price::from_job(&job_with("Hourly Range", "$10.00-$25.00")).unwrap();
+        assert_eq!(hourly.render(&config), "€5-€12.50/h ($10-$25/h)");
+
+        let fixed = Price::Fixed(amount(80.0, "EUR"));
+        assert_eq!(fixed.render(&config), "€80");
+
+        let unknown = Price::Fixed(amount(1000.0, "JPY"));
+        assert_eq!(unknown.render(&config), "¥1000");
+
+        let other = Price::Fixed(amount(12.0, "CHF"));
+        assert_eq!(other.to_string(), "CHF 12");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = |toml: &str| toml::from_str::<CurrencyConfig>(toml).unwrap().validate();
+
+        assert!(config("preferred = \"euro\"").is_err());
+        assert!(config("rates = { USD = 0.0 }").is_err());
+        assert!(config("rates = { dollars = 1.0 }").is_err());
+        assert!(config("preferred = \"EUR\"\nrates = { USD = 0.92 }").is_ok());
+    }
+}
@@ -0,0 +1,380 @@
+use std::{collections::HashMap, io::BufRead};
+
+use chrono::DateTime;
+use color_eyre::eyre::{self, bail, WrapErr};
+use serde::Deserialize;
+
+use super::{
+    fetch_jobs,
+    get_bytes::req_url,
+    parse_xml::{html_text, mapped_detail, parse_date, FeedEntry, JobStream},
+};
+use crate::JobPost;
+
+/// Maps the entries of one kind of job feed onto `JobPost`s.
+pub trait Source {
+    /// `None` skips an entry that is not a job post.
+    fn job(&self, entry: FeedEntry) -> Option<eyre::Result<JobPost>>;
+}
+
+/// Upwork's RSS feeds, with budget, category, country and the rest given as
+/// `<b>Label</b>: value` lines in the description.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Upwork;
+
+impl Source for Upwork {
+    /// Items without a description are skipped, and so are those with a bad
+    /// date, category or details, rather than failing the whole feed.
+    fn job(&self, entry: FeedEntry) -> Option<eyre::Result<JobPost>> {
+        let description = entry.description?;
+        let link = entry.link.clone();
+
+        let job = parse_date(&entry.published).and_then(|timestamp| {
+            mapped_detail(
+                entry.published,
+                timestamp,
+                entry.title,
+                entry.link,
+                description,
+            )
+        });
+
+        match job {
+            Ok(job) => Some(Ok(job)),
+            Err(err) => {
+                tracing::warn!(link, err = %format_args!("{:#}", err), "skipping invalid entry");
+                None
+            }
+        }
+    }
+}
+
+/// Any other RSS or Atom job board. The description becomes the details and
+/// the categories the skills, with the first of them as the category.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Generic;
+
+impl Source for Generic {
+    /// Entries without a link are skipped, there would be nothing to open, and
+    /// so are those with a date in neither RFC 2822 nor RFC 3339 form, rather
+    /// than failing the whole feed.
+    fn job(&self, entry: FeedEntry) -> Option<eyre::Result<JobPost>> {
+        let link = entry.link.trim();
+        if link.is_empty() {
+            return None;
+        }
+
+        let posted = if entry.published.trim().is_empty() {
+            entry.updated.trim()
+        } else {
+            entry.published.trim()
+        };
+        let timestamp = match DateTime::parse_from_rfc2822(posted)
+            .or_else(|_| DateTime::parse_from_rfc3339(posted))
+        {
+            Ok(date) => date.timestamp(),
+            Err(err) => {
+                tracing::warn!(link, date = posted, %err, "skipping entry with invalid date");
+                return None;
+            }
+        };
+
+        let categories: Vec<&str> = entry
+            .categories
+            .iter()
+            .map(|category| category.trim())
+            .filter(|category| !category.is_empty())
+            .collect();
+        let details = entry
+            .description
+            .as_deref()
+            .map(html_text)
+            .unwrap_or_default();
+
+        let mut detail = HashMap::from([("details".to_string(), details)]);
+        if let Some(category) = categories.first() {
+            detail.insert("Category".to_string(), category.to_string());
+            detail.insert("Skills".to_string(), categories.join(", "));
+        }
+
+        Some(Ok(JobPost {
+            title: entry.title.trim().to_owned(),
+            link: link.to_owned(),
+            category: categories
+                .first()
+                .map(|category| category.to_lowercase().replace(' ', "_"))
+                .unwrap_or_default(),
+            detail,
+            posted_on: posted.to_owned(),
+            posted_timestamp: timestamp,
+            country: None,
+            location_requirement: None,
+            id: Some(link_id(link)),
+        }))
+    }
+}
+
+/// A stable id for a link, safe to use as a key and in file names. FNV-1a,
+/// as it never changes between builds, unlike the standard library hasher.
+fn link_id(link: &str) -> String {
+    let hash = link.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    Upwork,
+    #[default]
+    Generic,
+}
+
+/// A feed fetched alongside the Upwork searches.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedConfig {
+    /// Shown in the status bar and the error log, like a search
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub source: SourceKind,
+}
+
+impl FeedConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("name: must not be empty");
+        }
+
+        let url = reqwest::Url::parse(&self.url)
+            .wrap_err_with(|| format!("url: `{}` is not a url", self.url))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("url: `{}` must use http or https", self.url);
+        }
+
+        Ok(())
+    }
+}
+
+/// What gets polled: an Upwork search or a configured feed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Subscription {
+    Search(String),
+    Feed(FeedConfig),
+}
+
+impl Subscription {
+    /// The search, or the feed's name, that its fetches are keyed by.
+    pub fn name(&self) -> &str {
+        match self {
+            Subscription::Search(query) => query,
+            Subscription::Feed(feed) => &feed.name,
+        }
+    }
+
+    pub async fn fetch(&self) -> eyre::Result<Vec<JobPost>> {
+        match self {
+            Subscription::Search(query) => fetch_jobs(query).await,
+            Subscription::Feed(feed) => fetch_feed(feed).await,
+        }
+    }
+}
+
+pub fn parse_feed<R: BufRead>(reader: R, source: SourceKind) -> eyre::Result<Vec<JobPost>> {
+    match source {
+        SourceKind::Upwork => JobStream::with_source(reader, Upwork).collect(),
+        SourceKind::Generic => JobStream::with_source(reader, Generic).collect(),
+    }
+}
+
+pub async fn fetch_feed(feed: &FeedConfig) -> eyre::Result<Vec<JobPost>> {
+    let byte_data = req_url(&feed.url).await?;
+    parse_feed(&byte_data[..], feed.source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_generic_rss() -> eyre::Result<()> {
+        let feed = fs::read("tests/fixtures/sources/remote_rss.xml")?;
+        let jobs = parse_feed(&feed[..], SourceKind::Generic)?;
+
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].title, "Senior Rust Engineer (Remote)");
+        assert_eq!(jobs[0].link, "https://remote.example.com/jobs/1042");
+        assert_eq!(jobs[0].id(), "cdda856ca23a759a");
+        assert_ne!(jobs[1].id(), jobs[0].id());
+        assert_eq!(jobs[0].posted_timestamp, 1714381200);
+        assert_eq!(jobs[0].category, "backend");
+        assert_eq!(jobs[0].detail["Skills"], "Backend, Rust, Tokio");
+        assert_eq!(
+            jobs[0].detail["details"],
+            "We build payment APIs in Rust & Tokio.\nFully remote, EU hours."
+        );
+        assert_eq!(jobs[1].title, "Part-time technical writer");
+        assert_eq!(jobs[1].link, "https://remote.example.com/jobs/1043");
+        assert_eq!(jobs[1].detail["details"], "Document our public API.");
+        assert_eq!(jobs[1].category, "");
+        assert!(!jobs[1].detail.contains_key("Skills"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generic_atom() -> eyre::Result<()> {
+        let feed = fs::read("tests/fixtures/sources/freelance_atom.xml")?;
+        let jobs = parse_feed(&feed[..], SourceKind::Generic)?;
+
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].title, "Logo for a bakery");
+        assert_eq!(jobs[0].link, "https://gigs.example.org/gig/77");
+        assert_eq!(jobs[0].posted_timestamp, 1714384800);
+        assert_eq!(jobs[0].category, "logo_design");
+        assert_eq!(jobs[0].detail["details"], "Fresh, warm and <hand-drawn>.");
+        // Without `<published>` the entry falls back to `<updated>`.
+        assert_eq!(jobs[1].posted_on, "2024-04-29T11:30:00+02:00");
+        assert_eq!(jobs[1].detail["details"], "Port a Python scraper to Rust.");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_date_skips_entry() -> eyre::Result<()> {
+        let feed = r#"<rss><channel>
+            <item><title>Undated</title><link>https://a.example/1</link><pubDate>soon</pubDate></item>
+            <item><title>Dated</title><link>https://a.example/2</link><pubDate>2024-04-29T10:00:00Z</pubDate></item>
+        </channel></rss>"#;
+
+        let jobs = parse_feed(feed.as_bytes(), SourceKind::Generic)?;
+
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].title, "Dated");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upwork_skips_invalid_items() -> eyre::Result<()> {
+        let item = |title: &str, description: &str, date: &str| {
+            format!(
+                "<item><title>{}</title><link>https://www.upwork.com/jobs/{}</link>\
+                 <description><![CDATA[{}]]></description><pubDate>{}</pubDate></item>",
+                title, title, description, date
+            )
+        };
+        let feed = format!(
+            "<rss><channel>{}{}{}</channel></rss>",
+            item("Undated", "<b>Category</b>: Design", "soon"),
+            item(
+                "Uncategorised",
+                "<b>Budget</b>: $50",
+                "Mon, 29 Apr 2024 09:00:00 +0000"
+            ),
+            item(
+                "Valid",
+                "<b>Category</b>: Design",
+                "Mon, 29 Apr 2024 09:00:00 +0000"
+            ),
+        );
+
+        let jobs = parse_feed(feed.as_bytes(), SourceKind::Upwork)?;
+
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].title, "Valid");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upwork_feed_as_source() -> eyre::Result<()> {
+        let feed = fs::read("job.xml")?;
+
+        assert_eq!(
+            parse_feed(&feed[..], SourceKind::Upwork)?,
+            crate::core_opt::parse_xml::parse_xml(&feed[..])?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_feed_subscription() -> eyre::Result<()> {
+        use tokio::{
+            io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+            net::TcpListener,
+        };
+
+        let body = fs::read("tests/fixtures/sources/freelance_atom.xml")?;
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                let line = line.trim_end().to_owned();
+                if line.is_empty() {
+                    break;
+                }
+                request.push(line);
+            }
+
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let mut stream = reader.into_inner();
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+
+            request
+        });
+
+        let subscription = Subscription::Feed(FeedConfig {
+            name: "gigs".to_string(),
+            url: format!("http://{}/feed.atom?tag=rust&sort=new", addr),
+            source: SourceKind::Generic,
+        });
+        let jobs = subscription.fetch().await?;
+        let request = server.await?;
+
+        assert_eq!(subscription.name(), "gigs");
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(request[0], "GET /feed.atom?tag=rust&sort=new HTTP/1.1");
+        assert!(request
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(&format!("host: {}", addr))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() {
+        let feed = FeedConfig {
+            name: "remote".to_string(),
+            url: "https://remote.example.com/jobs.rss".to_string(),
+            source: SourceKind::Generic,
+        };
+        assert!(feed.validate().is_ok());
+        assert!(FeedConfig {
+            url: "ftp://remote.example.com/jobs.rss".to_string(),
+            ..feed.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(FeedConfig {
+            name: " ".to_string(),
+            ..feed
+        }
+        .validate()
+        .is_err());
+    }
+}
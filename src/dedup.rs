@@ -0,0 +1,288 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use color_eyre::eyre::{self, bail};
+use serde::Deserialize;
+
+use crate::JobPost;
+
+const SHINGLE_WORDS: usize = 3;
+const BANDS: usize = 16;
+const ROWS: usize = 4;
+const SIGNATURE_LEN: usize = BANDS * ROWS;
+const SEED_STEP: u64 = 0x9e37_79b9_7f4a_7c15;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DedupConfig {
+    /// Show reposts under their original instead of as separate jobs
+    pub collapse: bool,
+    /// Estimated Jaccard similarity at which two jobs count as the same post
+    pub threshold: f64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            collapse: true,
+            threshold: 0.8,
+        }
+    }
+}
+
+impl DedupConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        if !(self.threshold > 0.0 && self.threshold <= 1.0) {
+            bail!("threshold: must be above 0 and at most 1");
+        }
+
+        Ok(())
+    }
+}
+
+/// MinHash signature over word shingles of a job's title and description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature([u64; SIGNATURE_LEN]);
+
+impl Signature {
+    /// `None` for a job without a single word, which would otherwise match
+    /// every other such job exactly.
+    pub fn of(job: &JobPost) -> Option<Self> {
+        let title = job.title.trim_end_matches(" - Upwork");
+        let description = job.detail.get("details").map(String::as_str);
+        let words: Vec<String> = [title, description.unwrap_or_default()]
+            .iter()
+            .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        let mut signature = [u64::MAX; SIGNATURE_LEN];
+        for shingle in words.windows(SHINGLE_WORDS.min(words.len())) {
+            let mut hasher = DefaultHasher::new();
+            shingle.hash(&mut hasher);
+            let hash = hasher.finish();
+
+            for (seed, min) in signature.iter_mut().enumerate() {
+                let seed = (seed as u64).wrapping_mul(SEED_STEP);
+                *min = (*min).min(mix(hash ^ seed));
+            }
+        }
+
+        Some(Self(signature))
+    }
+
+    /// Estimated Jaccard similarity of the two shingle sets, from 0 to 1.
+    pub fn similarity(&self, other: &Signature) -> f64 {
+        let equal = self.0.iter().zip(&other.0).filter(|(a, b)| a == b).count();
+
+        equal as f64 / SIGNATURE_LEN as f64
+    }
+
+    fn band(&self, band: usize) -> &[u64] {
+        &self.0[band * ROWS..(band + 1) * ROWS]
+    }
+}
+
+/// Groups of jobs that are likely the same post under different IDs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Duplicates {
+    /// Repost ID to the ID of the earliest posted job in its group
+    original: HashMap<String, String>,
+    /// Original ID to its reposts, oldest first
+    reposts: HashMap<String, Vec<String>>,
+}
+
+impl Duplicates {
+    pub fn detect<'a>(jobs: impl IntoIterator<Item = &'a JobPost>, threshold: f64) -> Self {
+        let jobs: Vec<&JobPost> = jobs.into_iter().collect();
+        let signatures: Vec<Option<Signature>> =
+            jobs.iter().map(|job| Signature::of(job)).collect();
+
+        let mut parent: Vec<usize> = (0..jobs.len()).collect();
+        let mut compared = HashSet::new();
+
+        for band in 0..BANDS {
+            let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+            for (index, signature) in signatures.iter().enumerate() {
+                if let Some(signature) = signature {
+                    buckets.entry(signature.band(band)).or_default().push(index);
+                }
+            }
+
+            for bucket in buckets.values().filter(|bucket| bucket.len() > 1) {
+                for (i, &a) in bucket.iter().enumerate() {
+                    for &b in &bucket[i + 1..] {
+                        let similarity = match (&signatures[a], &signatures[b]) {
+                            (Some(a), Some(b)) => a.similarity(b),
+                            _ => 0.0,
+                        };
+                        if compared.insert((a, b)) && similarity >= threshold {
+                            let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+                            parent[root_b] = root_a;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<&JobPost>> = HashMap::new();
+        for (index, job) in jobs.iter().enumerate() {
+            groups
+                .entry(find(&mut parent, index))
+                .or_default()
+                .push(job);
+        }
+
+        let mut duplicates = Self::default();
+        for mut group in groups.into_values().filter(|group| group.len() > 1) {
+            group.sort_by_key(|job| (job.posted_timestamp, job.id().to_owned()));
+            let original = group[0].id().to_owned();
+
+            for repost in &group[1..] {
+                duplicates
+                    .original
+                    .insert(repost.id().to_owned(), original.clone());
+            }
+            duplicates.reposts.insert(
+                original,
+                group[1..].iter().map(|job| job.id().to_owned()).collect(),
+            );
+        }
+
+        duplicates
+    }
+
+    pub fn original_of(&self, id: &str) -> Option<&str> {
+        self.original.get(id).map(String::as_str)
+    }
+
+    pub fn reposts_of(&self, id: &str) -> &[String] {
+        self.reposts.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+fn find(parent: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    parent[index] = root;
+
+    root
+}
+
+/// SplitMix64 finaliser, used to derive independent hash functions per seed.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::parse_xml::parse_xml;
+    use std::fs;
+
+    fn repost(job: &JobPost, id: &str, posted_timestamp: i64) -> JobPost {
+        JobPost {
+            link: format!("https://www.upwork.com/jobs/Repost_%7E{}", id),
+            posted_timestamp,
+            ..job.clone()
+        }
+    }
+
+    #[test]
+    fn test_detects_reposts_in_fixture() -> eyre::Result<()> {
+        let jobs = parse_xml(&fs::read("job.xml")?[..])?;
+        let duplicates = Duplicates::detect(&jobs, DedupConfig::default().threshold);
+
+        let mut perfume: Vec<&JobPost> = jobs
+            .iter()
+            .filter(|job| {
+                job.title
+                    .starts_with("Take photos and videos of your perfume")
+            })
+            .collect();
+        assert_eq!(perfume.len(), 2);
+        perfume.sort_by_key(|job| (job.posted_timestamp, job.id().to_owned()));
+
+        assert_eq!(
+            duplicates.original_of(perfume[1].id()),
+            Some(perfume[0].id())
+        );
+        assert_eq!(
+            duplicates.reposts_of(perfume[0].id()),
+            [perfume[1].id().to_owned()]
+        );
+
+        let grouped = jobs
+            .iter()
+            .filter(|job| duplicates.original_of(job.id()).is_some())
+            .count();
+        assert_eq!(grouped, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edited_repost_is_grouped_under_earliest() -> eyre::Result<()> {
+        let jobs = parse_xml(&fs::read("job.xml")?[..])?;
+        let job = jobs
+            .iter()
+            .find(|job| job.title.starts_with("Behance/dribbble designer"))
+            .unwrap();
+
+        let mut edited = repost(job, "edited", job.posted_timestamp + 60);
+        edited
+            .detail
+            .get_mut("details")
+            .unwrap()
+            .push_str(" Thanks!");
+        let earlier = repost(job, "earlier", job.posted_timestamp - 60);
+
+        let duplicates = Duplicates::detect([job, &edited, &earlier], 0.8);
+
+        assert_eq!(duplicates.original_of(job.id()), Some("earlier"));
+        assert_eq!(duplicates.original_of("edited"), Some("earlier"));
+        assert_eq!(
+            duplicates.reposts_of("earlier"),
+            [job.id().to_owned(), "edited".to_owned()]
+        );
+        let (signature, first) = (
+            Signature::of(job).unwrap(),
+            Signature::of(&jobs[0]).unwrap(),
+        );
+        assert!(signature.similarity(&first) < 0.2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jobs_without_words_are_not_duplicates() -> eyre::Result<()> {
+        let jobs = parse_xml(&fs::read("job.xml")?[..])?;
+        let mut empty = jobs[0].clone();
+        empty.title = String::new();
+        empty
+            .detail
+            .insert("details".to_string(), " - ".to_string());
+        let other = JobPost {
+            category: "unrelated".to_string(),
+            ..repost(&empty, "other", empty.posted_timestamp + 60)
+        };
+
+        let duplicates = Duplicates::detect([&empty, &other], 0.8);
+
+        assert_eq!(Signature::of(&empty), None);
+        assert_eq!(duplicates.original_of("other"), None);
+        assert!(duplicates.reposts_of(empty.id()).is_empty());
+
+        Ok(())
+    }
+}
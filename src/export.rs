@@ -0,0 +1,313 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use color_eyre::eyre;
+use serde::Serialize;
+
+use crate::{
+    core_opt::{price::Price, price_label},
+    JobPost,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Checklist,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown | ExportFormat::Checklist => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+const CSV_HEADER: [&str; 13] = [
+    "id",
+    "title",
+    "link",
+    "category",
+    "price_type",
+    "price_min",
+    "price_max",
+    "currency",
+    "skills",
+    "posted_on",
+    "posted_timestamp",
+    "country",
+    "location_requirement",
+];
+
+/// Prices are split into numbers and a currency code so spreadsheets can sort
+/// and sum them; the columns are empty when the job has no readable price.
+#[derive(Debug, Serialize)]
+struct CsvRow<'a> {
+    id: &'a str,
+    title: &'a str,
+    link: &'a str,
+    category: &'a str,
+    price_type: &'static str,
+    price_min: Option<f64>,
+    price_max: Option<f64>,
+    currency: Option<String>,
+    skills: &'a str,
+    posted_on: &'a str,
+    posted_timestamp: i64,
+    country: &'a str,
+    location_requirement: &'a str,
+}
+
+impl<'a> From<&'a JobPost> for CsvRow<'a> {
+    fn from(job: &'a JobPost) -> Self {
+        let (price_type, min, max) = match Price::from_job(job) {
+            Some(Price::Fixed(amount)) => ("fixed", Some(amount.clone()), Some(amount)),
+            Some(Price::Hourly { min, max }) => ("hourly", Some(min), Some(max)),
+            None => ("", None, None),
+        };
+
+        Self {
+            id: job.id(),
+            title: &job.title,
+            link: &job.link,
+            category: &job.category,
+            price_type,
+            price_min: min.as_ref().map(|amount| amount.value),
+            price_max: max.as_ref().map(|amount| amount.value),
+            currency: min.map(|amount| amount.currency),
+            skills: job.detail.get("Skills").map(String::as_str).unwrap_or(""),
+            posted_on: &job.posted_on,
+            posted_timestamp: job.posted_timestamp,
+            country: job.country.as_deref().unwrap_or(""),
+            location_requirement: job
+                .location_requirement
+                .as_ref()
+                .map(|requirement| requirement.text.as_str())
+                .unwrap_or(""),
+        }
+    }
+}
+
+pub fn export<W>(jobs: &[&JobPost], format: ExportFormat, writer: W) -> eyre::Result<()>
+where
+    W: Write,
+{
+    match format {
+        ExportFormat::Csv => write_csv(jobs, writer),
+        ExportFormat::Markdown => write_markdown_table(jobs, writer),
+        ExportFormat::Checklist => write_checklist(jobs, writer),
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(writer, jobs)?;
+            Ok(())
+        }
+    }
+}
+
+fn write_csv<W: Write>(jobs: &[&JobPost], writer: W) -> eyre::Result<()> {
+    // Written by hand so an empty export still has its header row.
+    let mut csv_writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(writer);
+    csv_writer.write_record(CSV_HEADER)?;
+
+    for job in jobs {
+        csv_writer.serialize(CsvRow::from(*job))?;
+    }
+
+    csv_writer.flush()?;
+
+    Ok(())
+}
+
+fn write_markdown_table<W: Write>(jobs: &[&JobPost], mut writer: W) -> eyre::Result<()> {
+    writeln!(writer, "| Title | Category | Price | Posted On |")?;
+    writeln!(writer, "| --- | --- | --- | --- |")?;
+
+    for job in jobs {
+        let category = job.detail.get("Category").unwrap_or(&job.category);
+
+        writeln!(
+            writer,
+            "| [{}]({}) | {} | {} | {} |",
+            escape_markdown(&job.title),
+            escape_link(&job.link),
+            escape_markdown(category),
+            escape_markdown(&price_label(job)),
+            escape_markdown(&job.posted_on)
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_checklist<W: Write>(jobs: &[&JobPost], mut writer: W) -> eyre::Result<()> {
+    for job in jobs {
+        writeln!(
+            writer,
+            "- [ ] [{}]({}) — {}",
+            escape_markdown(&job.title),
+            escape_link(&job.link),
+            escape_markdown(&price_label(job))
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Escapes text for a table cell or link text, where a `|` would end the
+/// cell, a bracket the link text and a line break the row.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for c in line.trim().chars() {
+            if matches!(c, '\\' | '|' | '[' | ']' | '(' | ')') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+
+    escaped
+}
+
+/// Percent-encodes the characters that would end a link destination early.
+fn escape_link(link: &str) -> String {
+    link.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace('<', "%3C")
+        .replace('>', "%3E")
+        .replace('|', "%7C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn job() -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Budget".to_string(), "$80".to_string());
+        detail.insert("Category".to_string(), "Presentation Design".to_string());
+        detail.insert(
+            "Skills".to_string(),
+            "Graphic Design, Microsoft PowerPoint".to_string(),
+        );
+
+        JobPost {
+            title: "Slides | urgent".to_string(),
+            link: "https://www.upwork.com/jobs/Slides_%7E0136d9648f43b2532a".to_string(),
+            category: "presentation_design".to_string(),
+            detail,
+            posted_on: "Mon, 29 Apr 2024 09:00:00 +0000".to_string(),
+            posted_timestamp: 1714381200,
+            country: Some("United States".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn export_to_string(format: ExportFormat) -> eyre::Result<String> {
+        let job = job();
+        let mut out = Vec::new();
+        export(&[&job], format, &mut out)?;
+
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn test_export_csv() -> eyre::Result<()> {
+        let out = export_to_string(ExportFormat::Csv)?;
+        let mut lines = out.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("id,title,link,category,price_type,price_min,price_max,currency,skills,posted_on,posted_timestamp,country,location_requirement")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("0136d9648f43b2532a,Slides | urgent,https://www.upwork.com/jobs/Slides_%7E0136d9648f43b2532a,presentation_design,fixed,80.0,80.0,USD,\"Graphic Design, Microsoft PowerPoint\",\"Mon, 29 Apr 2024 09:00:00 +0000\",1714381200,United States,")
+        );
+
+        let mut hourly = job();
+        hourly.detail.remove("Budget");
+        hourly
+            .detail
+            .insert("Hourly Range".to_string(), "$15.00-$30.00".to_string());
+        let mut unpriced = job();
+        unpriced.detail.remove("Budget");
+        let mut out = Vec::new();
+        export(&[&hourly, &unpriced], ExportFormat::Csv, &mut out)?;
+        let out = String::from_utf8(out)?;
+        let rows: Vec<Vec<&str>> = out
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').skip(4).take(4).collect())
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![vec!["hourly", "15.0", "30.0", "USD"], vec!["", "", "", ""]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_empty_csv_has_header() -> eyre::Result<()> {
+        let mut out = Vec::new();
+        export(&[], ExportFormat::Csv, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            format!("{}\n", CSV_HEADER.join(","))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_markdown() -> eyre::Result<()> {
+        let out = export_to_string(ExportFormat::Markdown)?;
+
+        assert!(out.contains("| [Slides \\| urgent](https://www.upwork.com/jobs/Slides_%7E0136d9648f43b2532a) | Presentation Design | Budget : $80 |"));
+
+        let out = export_to_string(ExportFormat::Checklist)?;
+
+        assert!(out.starts_with("- [ ] [Slides \\| urgent]"));
+
+        let mut job = job();
+        job.title = "Fix [docs](x) |\nurgent".to_string();
+        job.link = "https://example.com/jobs/a (b)".to_string();
+        let mut out = Vec::new();
+        export(&[&job], ExportFormat::Markdown, &mut out)?;
+        let row = String::from_utf8(out)?
+            .lines()
+            .nth(2)
+            .unwrap_or_default()
+            .to_owned();
+
+        assert!(row.starts_with(
+            "| [Fix \\[docs\\]\\(x\\) \\| urgent](https://example.com/jobs/a%20%28b%29) |"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_json() -> eyre::Result<()> {
+        let out = export_to_string(ExportFormat::Json)?;
+        let parsed: Vec<JobPost> = serde_json::from_str(&out)?;
+
+        assert_eq!(parsed, vec![job()]);
+
+        Ok(())
+    }
+}
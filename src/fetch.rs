@@ -0,0 +1,237 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{self, bail};
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use crate::JobPost;
+
+/// Far more than any rate limit lets through, and well under what
+/// `Semaphore` can hold.
+const MAX_CONCURRENCY: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FetchConfig {
+    /// Searches fetched at the same time
+    pub concurrency: usize,
+    /// Requests that may start back to back after a quiet spell
+    pub burst: u32,
+    /// Time for one more request to be allowed, shared by all searches
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 3,
+            burst: 3,
+            interval: Duration::from_secs(2),
+        }
+    }
+}
+
+impl FetchConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.concurrency == 0 {
+            bail!("concurrency: must be at least 1");
+        }
+
+        if self.concurrency > MAX_CONCURRENCY {
+            bail!("concurrency: must be at most {}", MAX_CONCURRENCY);
+        }
+
+        if self.burst == 0 {
+            bail!("burst: must be at least 1");
+        }
+
+        if self.interval.is_zero() {
+            bail!("interval: must be longer than 0s");
+        }
+
+        Ok(())
+    }
+}
+
+/// Allows `capacity` requests at once, refilling one every `interval`.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    interval: Duration,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, interval: Duration, now: Instant) -> Self {
+        Self {
+            capacity: f64::from(capacity),
+            tokens: f64::from(capacity),
+            interval,
+            updated: now,
+        }
+    }
+
+    /// Takes a token, or tells how long until the next one is due.
+    pub fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() / self.interval.as_secs_f64()).min(self.capacity);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(self.interval.mul_f64(1.0 - self.tokens))
+        }
+    }
+}
+
+/// Runs fetches under a concurrency cap and a shared rate limit. Clones
+/// share both, so every task spawned from one scheduler counts together.
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    permits: Arc<Semaphore>,
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl Scheduler {
+    pub fn new(config: &FetchConfig) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(config.concurrency)),
+            bucket: Arc::new(Mutex::new(TokenBucket::new(
+                config.burst,
+                config.interval,
+                Instant::now(),
+            ))),
+        }
+    }
+
+    /// Waits for a free slot and a token, then runs `fetch`.
+    pub async fn run<F: Future>(&self, fetch: F) -> F::Output {
+        // The semaphore is never closed, so acquiring can only wait.
+        let _permit = self.permits.acquire().await;
+
+        loop {
+            let taken = self
+                .bucket
+                .lock()
+                .expect("token bucket lock poisoned")
+                .take(Instant::now());
+
+            match taken {
+                Ok(()) => break,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+
+        fetch.await
+    }
+}
+
+/// How the last fetches of one search went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryStatus {
+    pub in_flight: bool,
+    pub last_fetched: Option<i64>,
+    /// Jobs in the last successful fetch
+    pub items: usize,
+    /// Cleared again by the next successful fetch
+    pub last_error: Option<String>,
+}
+
+impl QueryStatus {
+    pub fn finish(&mut self, result: &eyre::Result<Vec<JobPost>>, now: i64) {
+        self.in_flight = false;
+
+        match result {
+            Ok(jobs) => {
+                self.last_fetched = Some(now);
+                self.items = jobs.len();
+                self.last_error = None;
+            }
+            Err(err) => self.last_error = Some(format!("{:#}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_token_bucket_refills() {
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        let mut bucket = TokenBucket::new(2, second, start);
+
+        assert_eq!(bucket.take(start), Ok(()));
+        assert_eq!(bucket.take(start), Ok(()));
+        assert_eq!(bucket.take(start), Err(second));
+
+        let later = start + second / 4;
+        assert_eq!(bucket.take(later), Err(second * 3 / 4));
+        assert_eq!(bucket.take(start + second), Ok(()));
+
+        // Idle time never banks more than the burst.
+        let idle = start + second * 60;
+        assert_eq!(bucket.take(idle), Ok(()));
+        assert_eq!(bucket.take(idle), Ok(()));
+        assert!(bucket.take(idle).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_caps_concurrency() {
+        let scheduler = Scheduler::new(&FetchConfig {
+            concurrency: 2,
+            burst: 6,
+            interval: Duration::from_millis(1),
+        });
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..6)
+            .map(|_| {
+                let (scheduler, running, peak) = (scheduler.clone(), running.clone(), peak.clone());
+                tokio::spawn(async move {
+                    scheduler
+                        .run(async {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            peak.fetch_max(now, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            running.fetch_sub(1, Ordering::SeqCst);
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_query_status_keeps_last_success() {
+        let mut status = QueryStatus {
+            in_flight: true,
+            ..QueryStatus::default()
+        };
+
+        status.finish(&Ok(Vec::new()), 100);
+        status.in_flight = true;
+        status.finish(&Err(eyre::eyre!("429 Too Many Requests")), 200);
+
+        assert!(!status.in_flight);
+        assert_eq!(status.last_fetched, Some(100));
+        assert_eq!(status.last_error.as_deref(), Some("429 Too Many Requests"));
+    }
+}
@@ -0,0 +1,368 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{self, bail, eyre, WrapErr};
+use rusqlite::{params, Connection};
+
+use crate::{skills::job_skills, JobPost};
+
+/// Schema changes in order. `PRAGMA user_version` records how many have been
+/// applied, so only append to this list.
+const MIGRATIONS: [&str; 2] = [
+    "CREATE TABLE jobs (
+        pk INTEGER PRIMARY KEY,
+        id TEXT NOT NULL UNIQUE,
+        title TEXT NOT NULL,
+        description TEXT NOT NULL,
+        category TEXT NOT NULL,
+        country TEXT,
+        posted_timestamp INTEGER NOT NULL,
+        first_seen INTEGER NOT NULL,
+        last_seen INTEGER NOT NULL,
+        post TEXT NOT NULL
+    );
+    CREATE INDEX jobs_posted ON jobs (posted_timestamp);
+    CREATE TABLE job_skills (
+        job_id TEXT NOT NULL REFERENCES jobs (id),
+        skill TEXT NOT NULL COLLATE NOCASE,
+        PRIMARY KEY (job_id, skill)
+    );
+    CREATE INDEX job_skills_skill ON job_skills (skill);",
+    "CREATE VIRTUAL TABLE jobs_fts USING fts5 (
+        title, description, content = 'jobs', content_rowid = 'pk'
+    );
+    CREATE TRIGGER jobs_fts_insert AFTER INSERT ON jobs BEGIN
+        INSERT INTO jobs_fts (rowid, title, description)
+        VALUES (new.pk, new.title, new.description);
+    END;
+    CREATE TRIGGER jobs_fts_delete AFTER DELETE ON jobs BEGIN
+        INSERT INTO jobs_fts (jobs_fts, rowid, title, description)
+        VALUES ('delete', old.pk, old.title, old.description);
+    END;
+    CREATE TRIGGER jobs_fts_update AFTER UPDATE ON jobs BEGIN
+        INSERT INTO jobs_fts (jobs_fts, rowid, title, description)
+        VALUES ('delete', old.pk, old.title, old.description);
+        INSERT INTO jobs_fts (rowid, title, description)
+        VALUES (new.pk, new.title, new.description);
+    END;
+    INSERT INTO jobs_fts (jobs_fts) VALUES ('rebuild');",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryQuery {
+    /// Words that must all appear in the title or description; a trailing
+    /// `*` matches by prefix. Empty lists the latest jobs.
+    pub text: String,
+    pub skill: Option<String>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryHit {
+    pub post: JobPost,
+    pub first_seen: i64,
+}
+
+/// Every job ever fetched, kept in SQLite for search beyond the store's
+/// retention window.
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn default_path() -> eyre::Result<PathBuf> {
+        let data_dir = dirs::data_dir().ok_or_else(|| eyre!("data directory not found"))?;
+        Ok(data_dir.join("upwork-tui").join("history.db"))
+    }
+
+    pub fn open(path: &Path) -> eyre::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path)
+            .wrap_err_with(|| format!("opening history at {}", path.display()))?;
+
+        Self::with_connection(conn)
+    }
+
+    pub fn open_in_memory() -> eyre::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(mut conn: Connection) -> eyre::Result<Self> {
+        migrate(&mut conn)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Adds or refreshes jobs, each with the time it was seen.
+    pub fn record<'a>(
+        &mut self,
+        jobs: impl IntoIterator<Item = (&'a JobPost, i64)>,
+    ) -> eyre::Result<()> {
+        let tx = self.conn.transaction()?;
+
+        {
+            let mut upsert = tx.prepare_cached(
+                "INSERT INTO jobs (id, title, description, category, country,
+                    posted_timestamp, first_seen, last_seen, post)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?8)
+                ON CONFLICT (id) DO UPDATE SET
+                    title = excluded.title,
+                    description = excluded.description,
+                    category = excluded.category,
+                    country = excluded.country,
+                    posted_timestamp = excluded.posted_timestamp,
+                    first_seen = min(first_seen, excluded.first_seen),
+                    last_seen = max(last_seen, excluded.last_seen),
+                    post = excluded.post",
+            )?;
+            let mut clear_skills = tx.prepare_cached("DELETE FROM job_skills WHERE job_id = ?1")?;
+            let mut add_skill = tx.prepare_cached(
+                "INSERT OR IGNORE INTO job_skills (job_id, skill) VALUES (?1, ?2)",
+            )?;
+
+            for (job, seen) in jobs {
+                let description = job.detail.get("details").map(String::as_str);
+
+                upsert.execute(params![
+                    job.id(),
+                    job.title,
+                    description.unwrap_or_default(),
+                    job.category,
+                    job.country,
+                    job.posted_timestamp,
+                    seen,
+                    serde_json::to_string(job)?,
+                ])?;
+
+                clear_skills.execute([job.id()])?;
+                for skill in job_skills(job) {
+                    add_skill.execute([job.id(), skill])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn count(&self) -> eyre::Result<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT count(*) FROM jobs", [], |row| row.get(0))?;
+
+        Ok(count as usize)
+    }
+
+    /// Jobs posted at or after `since`, oldest first.
+    pub fn posted_since(&self, since: i64) -> eyre::Result<Vec<JobPost>> {
+        let mut statement = self.conn.prepare(
+            "SELECT post FROM jobs WHERE posted_timestamp >= ?1 ORDER BY posted_timestamp",
+        )?;
+        let rows = statement.query_map([since], |row| row.get::<_, String>(0))?;
+
+        rows.map(|post| Ok(serde_json::from_str(&post?)?)).collect()
+    }
+
+    /// Matching jobs, best match first, or newest first without search text.
+    pub fn search(&self, query: &HistoryQuery) -> eyre::Result<Vec<HistoryHit>> {
+        let text = fts_query(&query.text);
+        let skill_filter = "(?2 IS NULL OR EXISTS (
+            SELECT 1 FROM job_skills WHERE job_id = jobs.id AND skill = ?2
+        ))";
+
+        let sql = if text.is_empty() {
+            format!(
+                "SELECT post, first_seen FROM jobs
+                WHERE ?1 = '' AND {}
+                ORDER BY posted_timestamp DESC LIMIT ?3",
+                skill_filter
+            )
+        } else {
+            format!(
+                "SELECT jobs.post, jobs.first_seen FROM jobs_fts
+                JOIN jobs ON jobs.pk = jobs_fts.rowid
+                WHERE jobs_fts MATCH ?1 AND {}
+                ORDER BY jobs_fts.rank, jobs.posted_timestamp DESC LIMIT ?3",
+                skill_filter
+            )
+        };
+
+        let mut statement = self.conn.prepare(&sql)?;
+        let rows = statement.query_map(params![text, query.skill, query.limit as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        rows.map(|row| {
+            let (post, first_seen) = row?;
+            Ok(HistoryHit {
+                post: serde_json::from_str(&post)?,
+                first_seen,
+            })
+        })
+        .collect()
+    }
+}
+
+fn migrate(conn: &mut Connection) -> eyre::Result<()> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let version = version as usize;
+    if version > MIGRATIONS.len() {
+        bail!(
+            "history schema version {} is newer than this build supports ({})",
+            version,
+            MIGRATIONS.len()
+        );
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)
+            .wrap_err_with(|| format!("applying history migration {}", index + 1))?;
+        tx.pragma_update(None, "user_version", index as i64 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Quotes each word so punctuation like `c++` or `-` is searched literally
+/// instead of being read as FTS5 syntax.
+fn fts_query(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| match word.strip_suffix('*') {
+            Some(prefix) if !prefix.is_empty() => format!("\"{}\"*", prefix.replace('"', "\"\"")),
+            _ => format!("\"{}\"", word.replace('"', "\"\"")),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::parse_xml::parse_xml;
+    use std::env;
+
+    fn history() -> eyre::Result<(History, Vec<JobPost>)> {
+        let jobs = parse_xml(&fs::read("job.xml")?[..])?;
+        let mut history = History::open_in_memory()?;
+        history.record(jobs.iter().map(|job| (job, 100)))?;
+
+        Ok((history, jobs))
+    }
+
+    fn history_job() -> JobPost {
+        JobPost {
+            title: "Rust developer".to_string(),
+            link: "https://www.upwork.com/jobs/Rust_%7E01".to_string(),
+            detail: Default::default(),
+            ..Default::default()
+        }
+    }
+
+    fn query(text: &str) -> HistoryQuery {
+        HistoryQuery {
+            text: text.to_string(),
+            skill: None,
+            limit: 50,
+        }
+    }
+
+    fn titles(hits: &[HistoryHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.post.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_full_text_search() -> eyre::Result<()> {
+        let (history, jobs) = history()?;
+        assert_eq!(history.count()?, jobs.len());
+
+        let perfume = history.search(&query("perfume storage"))?;
+        assert_eq!(perfume.len(), 2);
+        assert!(titles(&perfume)
+            .iter()
+            .all(|title| title.contains("perfume")));
+
+        let prefix = history.search(&query("fintec*"))?;
+        assert_eq!(prefix.len(), 2);
+
+        assert!(history.search(&query("\"unbalanced - c++"))?.is_empty());
+
+        let latest = history.search(&HistoryQuery {
+            limit: 3,
+            ..query("")
+        })?;
+        assert_eq!(latest.len(), 3);
+        assert!(latest[0].post.posted_timestamp >= latest[2].post.posted_timestamp);
+
+        let recent = history.posted_since(jobs[0].posted_timestamp)?;
+        assert!(recent
+            .iter()
+            .all(|job| job.posted_timestamp >= jobs[0].posted_timestamp));
+        assert!(recent.contains(&jobs[0]));
+
+        let skilled = history.search(&HistoryQuery {
+            skill: Some("adobe photoshop".to_string()),
+            ..query("designer")
+        })?;
+        assert!(!skilled.is_empty());
+        assert!(skilled
+            .iter()
+            .all(|hit| job_skills(&hit.post).contains(&"Adobe Photoshop")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_updates_in_place() -> eyre::Result<()> {
+        let (mut history, jobs) = history()?;
+
+        let mut edited = jobs[0].clone();
+        edited.title = "Renamed listing".to_string();
+        history.record([(&edited, 50), (&jobs[1], 200)])?;
+
+        assert_eq!(history.count()?, jobs.len());
+        let hits = history.search(&query("renamed"))?;
+        assert_eq!(titles(&hits), vec!["Renamed listing"]);
+        assert_eq!(hits[0].first_seen, 50);
+
+        let seen_again = history
+            .search(&query(&jobs[1].title))?
+            .into_iter()
+            .find(|hit| hit.post.id() == jobs[1].id());
+        assert_eq!(seen_again.map(|hit| hit.first_seen), Some(100));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrations_run_once() -> eyre::Result<()> {
+        let path = env::temp_dir().join(format!("upwork-tui-history-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        {
+            let mut history = History::open(&path)?;
+            history.record([(&history_job(), 1)])?;
+        }
+        let history = History::open(&path)?;
+        let version: i64 = history
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        assert_eq!(version as usize, MIGRATIONS.len());
+        assert_eq!(history.count()?, 1);
+
+        history.conn.pragma_update(None, "user_version", 99)?;
+        drop(history);
+        assert!(History::open(&path).is_err());
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+}
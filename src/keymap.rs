@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::{self, bail, eyre, WrapErr};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Next,
+    Prev,
+    First,
+    Last,
+    PageDown,
+    PageUp,
+    Open,
+    Star,
+    Hide,
+    Note,
+    NextView,
+    Board,
+    History,
+    Left,
+    Right,
+    Advance,
+    Retreat,
+    MarkLost,
+    Draft,
+    CopyDraft,
+    Search,
+    Refresh,
+    ToggleExpired,
+    ToggleRank,
+    Export,
+    ErrorLog,
+    Logs,
+    Help,
+}
+
+impl Action {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Next => "Next job",
+            Action::Prev => "Previous job",
+            Action::First => "First job",
+            Action::Last => "Last job",
+            Action::PageDown => "Page down",
+            Action::PageUp => "Page up",
+            Action::Open => "Open job in browser",
+            Action::Star => "Star or unstar job",
+            Action::Hide => "Hide or unhide job",
+            Action::Note => "Edit note on job",
+            Action::NextView => "Cycle all, starred, notes and hidden views",
+            Action::Board => "Toggle the pipeline board",
+            Action::History => "Toggle searching the full job history",
+            Action::Left => "Previous board column",
+            Action::Right => "Next board column",
+            Action::Advance => "Move job to the next stage",
+            Action::Retreat => "Move job back a stage",
+            Action::MarkLost => "Mark job as lost",
+            Action::Draft => "Edit proposal draft in $EDITOR",
+            Action::CopyDraft => "Copy proposal draft to the clipboard",
+            Action::Search => "Search titles and descriptions",
+            Action::Refresh => "Refresh searches now",
+            Action::ToggleExpired => "Show or hide expired jobs",
+            Action::ToggleRank => "Rank jobs by score or list newest first",
+            Action::Export => "Export the list",
+            Action::ErrorLog => "Show every error since starting",
+            Action::Logs => "Show the log, tab cycles the level",
+            Action::Help => "Toggle this help",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    #[default]
+    Vim,
+    Emacs,
+    Arrows,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeybindingConfig {
+    pub preset: Preset,
+    pub keys: HashMap<String, Action>,
+}
+
+type Key = (KeyCode, KeyModifiers);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: HashMap<Key, Action>,
+}
+
+impl Keymap {
+    pub fn from_config(config: &KeybindingConfig) -> eyre::Result<Self> {
+        let mut keymap = Self::preset(config.preset);
+
+        for (key, action) in &config.keys {
+            let key = parse_key(key).wrap_err_with(|| format!("keybindings.keys.\"{}\"", key))?;
+            keymap.bindings.insert(key, *action);
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn preset(preset: Preset) -> Self {
+        let bindings: &[(&str, Action)] = match preset {
+            Preset::Vim => &[
+                ("q", Action::Quit),
+                ("j", Action::Next),
+                ("k", Action::Prev),
+                ("g", Action::First),
+                ("G", Action::Last),
+                ("ctrl-d", Action::PageDown),
+                ("ctrl-u", Action::PageUp),
+                ("enter", Action::Open),
+                ("o", Action::Open),
+                ("s", Action::Star),
+                ("d", Action::Hide),
+                ("n", Action::Note),
+                ("tab", Action::NextView),
+                ("b", Action::Board),
+                ("H", Action::History),
+                ("h", Action::Left),
+                ("l", Action::Right),
+                (">", Action::Advance),
+                ("<", Action::Retreat),
+                ("L", Action::MarkLost),
+                ("p", Action::Draft),
+                ("y", Action::CopyDraft),
+                ("/", Action::Search),
+                ("r", Action::Refresh),
+                ("x", Action::ToggleExpired),
+                ("S", Action::ToggleRank),
+                ("e", Action::Export),
+                ("E", Action::ErrorLog),
+                ("T", Action::Logs),
+                ("?", Action::Help),
+            ],
+            Preset::Emacs => &[
+                ("q", Action::Quit),
+                ("ctrl-n", Action::Next),
+                ("ctrl-p", Action::Prev),
+                ("alt-<", Action::First),
+                ("alt->", Action::Last),
+                ("ctrl-v", Action::PageDown),
+                ("alt-v", Action::PageUp),
+                ("enter", Action::Open),
+                ("alt-s", Action::Star),
+                ("alt-k", Action::Hide),
+                ("alt-;", Action::Note),
+                ("alt-o", Action::NextView),
+                ("alt-p", Action::Board),
+                ("alt-h", Action::History),
+                ("ctrl-b", Action::Left),
+                ("ctrl-f", Action::Right),
+                (">", Action::Advance),
+                ("<", Action::Retreat),
+                ("alt-l", Action::MarkLost),
+                ("alt-e", Action::Draft),
+                ("alt-c", Action::CopyDraft),
+                ("ctrl-s", Action::Search),
+                ("alt-g", Action::Refresh),
+                ("alt-x", Action::ToggleExpired),
+                ("alt-r", Action::ToggleRank),
+                ("alt-w", Action::Export),
+                ("alt-m", Action::ErrorLog),
+                ("alt-t", Action::Logs),
+                ("?", Action::Help),
+            ],
+            Preset::Arrows => &[
+                ("q", Action::Quit),
+                ("down", Action::Next),
+                ("up", Action::Prev),
+                ("home", Action::First),
+                ("end", Action::Last),
+                ("pagedown", Action::PageDown),
+                ("pageup", Action::PageUp),
+                ("enter", Action::Open),
+                ("s", Action::Star),
+                ("delete", Action::Hide),
+                ("n", Action::Note),
+                ("tab", Action::NextView),
+                ("f2", Action::Board),
+                ("f3", Action::History),
+                ("left", Action::Left),
+                ("right", Action::Right),
+                (">", Action::Advance),
+                ("<", Action::Retreat),
+                ("L", Action::MarkLost),
+                ("p", Action::Draft),
+                ("y", Action::CopyDraft),
+                ("/", Action::Search),
+                ("f5", Action::Refresh),
+                ("x", Action::ToggleExpired),
+                ("S", Action::ToggleRank),
+                ("e", Action::Export),
+                ("f4", Action::ErrorLog),
+                ("f6", Action::Logs),
+                ("f1", Action::Help),
+                ("?", Action::Help),
+            ],
+        };
+
+        let bindings = bindings
+            .iter()
+            .map(|(key, action)| (parse_key(key).expect("preset keys are valid"), *action))
+            .collect();
+
+        Self { bindings }
+    }
+
+    pub fn get(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .get(&normalize(key.code, key.modifiers))
+            .copied()
+    }
+
+    pub fn key_for(&self, action: Action) -> Option<String> {
+        self.help()
+            .into_iter()
+            .find(|(_, bound)| *bound == action)
+            .map(|(keys, _)| keys)
+    }
+
+    pub fn help(&self) -> Vec<(String, Action)> {
+        let mut by_action: HashMap<Action, Vec<String>> = HashMap::new();
+
+        for (key, action) in &self.bindings {
+            by_action.entry(*action).or_default().push(format_key(key));
+        }
+
+        let mut help: Vec<(String, Action)> = by_action
+            .into_iter()
+            .map(|(action, mut keys)| {
+                keys.sort();
+                (keys.join(", "), action)
+            })
+            .collect();
+
+        help.sort_by_key(|(_, action)| *action);
+
+        help
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::default())
+    }
+}
+
+pub fn parse_key(raw: &str) -> eyre::Result<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = raw;
+
+    while let Some((prefix, tail)) = rest.split_once('-') {
+        if tail.is_empty() {
+            break;
+        }
+
+        modifiers |= match prefix.to_lowercase().as_str() {
+            "ctrl" | "c" => KeyModifiers::CONTROL,
+            "alt" | "m" => KeyModifiers::ALT,
+            "shift" | "s" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        rest = tail;
+    }
+
+    let code = match rest.to_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        function if function.len() > 1 && function.starts_with('f') => {
+            let number = function[1..]
+                .parse()
+                .map_err(|_| eyre!("unknown key `{}`", raw))?;
+            KeyCode::F(number)
+        }
+        _ => {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => bail!("unknown key `{}`", raw),
+            }
+        }
+    };
+
+    Ok(normalize(code, modifiers))
+}
+
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> Key {
+    match code {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => (
+            KeyCode::Char(c.to_ascii_uppercase()),
+            modifiers - KeyModifiers::SHIFT,
+        ),
+        KeyCode::BackTab => (code, modifiers - KeyModifiers::SHIFT),
+        _ => (code, modifiers),
+    }
+}
+
+fn format_key((code, modifiers): &Key) -> String {
+    let mut out = String::new();
+
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        out.push_str("ctrl-");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        out.push_str("alt-");
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        out.push_str("shift-");
+    }
+
+    match code {
+        KeyCode::Char(' ') => out.push_str("space"),
+        KeyCode::Char(c) => out.push(*c),
+        KeyCode::F(n) => out.push_str(&format!("f{}", n)),
+        other => out.push_str(&format!("{:?}", other).to_lowercase()),
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() -> eyre::Result<()> {
+        assert_eq!(parse_key("j")?, (KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(parse_key("G")?, (KeyCode::Char('G'), KeyModifiers::NONE));
+        assert_eq!(
+            parse_key("shift-g")?,
+            (KeyCode::Char('G'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("ctrl-n")?,
+            (KeyCode::Char('n'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse_key("ctrl-alt-x")?,
+            (
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            )
+        );
+        assert_eq!(parse_key("alt--")?, (KeyCode::Char('-'), KeyModifiers::ALT));
+        assert_eq!(parse_key("-")?, (KeyCode::Char('-'), KeyModifiers::NONE));
+        assert_eq!(
+            parse_key("PageDown")?,
+            (KeyCode::PageDown, KeyModifiers::NONE)
+        );
+        assert_eq!(parse_key("f5")?, (KeyCode::F(5), KeyModifiers::NONE));
+        assert!(parse_key("hyper-x").is_err());
+        assert!(parse_key("fx").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_presets_resolve_key_events() {
+        let shift_g = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(
+            Keymap::preset(Preset::Vim).get(&shift_g),
+            Some(Action::Last)
+        );
+
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(
+            Keymap::preset(Preset::Emacs).get(&ctrl_n),
+            Some(Action::Next)
+        );
+        assert_eq!(Keymap::preset(Preset::Vim).get(&ctrl_n), None);
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(
+            Keymap::preset(Preset::Arrows).get(&down),
+            Some(Action::Next)
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_preset() -> eyre::Result<()> {
+        let config: KeybindingConfig = toml::from_str(
+            r#"
+            preset = "emacs"
+            keys = { "ctrl-r" = "refresh", "q" = "quit" }
+            "#,
+        )?;
+        let keymap = Keymap::from_config(&config)?;
+
+        let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.get(&ctrl_r), Some(Action::Refresh));
+
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(keymap.get(&q), Some(Action::Quit));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let unknown_action = toml::from_str::<KeybindingConfig>(r#"keys = { "j" = "jump" }"#);
+        assert!(unknown_action.is_err());
+
+        let config = KeybindingConfig {
+            keys: HashMap::from([("hyper-j".to_string(), Action::Next)]),
+            ..Default::default()
+        };
+        assert!(Keymap::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_help_lists_every_action() {
+        let help = Keymap::preset(Preset::Vim).help();
+
+        assert!(help.contains(&("enter, o".to_string(), Action::Open)));
+        assert!(help.contains(&("ctrl-d".to_string(), Action::PageDown)));
+        assert_eq!(help.first().map(|(_, action)| *action), Some(Action::Quit));
+    }
+}
@@ -2,9 +2,31 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-mod core_opt;
+use crate::core_opt::location::LocationRequirement;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub mod app;
+pub mod cli;
+pub mod config;
+pub mod core_opt;
+pub mod dedup;
+pub mod export;
+pub mod fetch;
+pub mod history;
+pub mod keymap;
+pub mod logging;
+pub mod pipeline;
+pub mod proposal;
+pub mod report;
+pub mod scoring;
+pub mod serve;
+pub mod skills;
+pub mod store;
+pub mod template;
+pub mod theme;
+pub mod tui;
+pub mod webhook;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct JobPost {
     pub title: String,
     pub link: String,
@@ -12,6 +34,28 @@ pub struct JobPost {
     pub detail: HashMap<String, String>,
     pub posted_on: String,
     pub posted_timestamp: i64,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub location_requirement: Option<LocationRequirement>,
+    /// Set by sources whose links carry no id of their own; Upwork's is read
+    /// from the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl JobPost {
+    pub fn id(&self) -> &str {
+        if let Some(id) = &self.id {
+            return id;
+        }
+
+        self.link
+            .rsplit_once("%7E")
+            .or_else(|| self.link.rsplit_once('~'))
+            .map(|(_, id)| id)
+            .unwrap_or(&self.link)
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -21,3 +65,39 @@ pub struct FinalPost {
     detail: String,
     price: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job_with_link(link: &str) -> JobPost {
+        JobPost {
+            title: "Title".to_string(),
+            link: link.to_string(),
+            category: "web_design".to_string(),
+            posted_on: "Fri, 01 Sep 2023 02:19:13 +0000".to_string(),
+            posted_timestamp: 1693534753,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_job_id() {
+        let job =
+            job_with_link("https://www.upwork.com/jobs/Website-Hero-Page_%7E014431774d3a21a1a2");
+        assert_eq!(job.id(), "014431774d3a21a1a2");
+
+        let job =
+            job_with_link("https://www.upwork.com/jobs/Website-Hero-Page_~014431774d3a21a1a2");
+        assert_eq!(job.id(), "014431774d3a21a1a2");
+
+        let job = job_with_link("https://linktest.com");
+        assert_eq!(job.id(), "https://linktest.com");
+
+        let job = JobPost {
+            id: Some("cdda856ca23a759a".to_string()),
+            ..job
+        };
+        assert_eq!(job.id(), "cdda856ca23a759a");
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use color_eyre::eyre::{self, bail, eyre};
+use serde::Deserialize;
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::format::FmtSpan, layer::Context, prelude::*, EnvFilter, Layer,
+};
+
+const BUFFER_LEN: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// `RUST_LOG` style directives, e.g. `info` or `upwork_tui::core_opt=debug`
+    pub level: String,
+    /// Daily log files kept before the oldest is removed
+    pub max_files: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            max_files: 7,
+        }
+    }
+}
+
+impl LogConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        EnvFilter::try_new(&self.level).map_err(|err| eyre!("level: {}", err))?;
+
+        if self.max_files == 0 {
+            bail!("max_files: must be at least 1");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub at: i64,
+    pub level: Level,
+    pub target: String,
+    /// The message followed by the other fields as `name=value`
+    pub message: String,
+}
+
+/// The most recent records, kept for the in-app log viewer while the TUI owns
+/// the terminal.
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+}
+
+impl LogBuffer {
+    /// Records at `level` or more severe, newest first.
+    pub fn at_least(&self, level: Level) -> Vec<LogRecord> {
+        self.records
+            .lock()
+            .expect("log buffer lock poisoned")
+            .iter()
+            .rev()
+            .filter(|record| record.level <= level)
+            .cloned()
+            .collect()
+    }
+
+    fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().expect("log buffer lock poisoned");
+        if records.len() == BUFFER_LEN {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBuffer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        self.push(LogRecord {
+            at: Utc::now().timestamp(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_owned(),
+            message: visitor.message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.message.is_empty() {
+            self.message.push(' ');
+        }
+
+        let _ = if field.name() == "message" {
+            write!(self.message, "{:?}", value)
+        } else {
+            write!(self.message, "{}={:?}", field.name(), value)
+        };
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+}
+
+pub fn log_dir() -> eyre::Result<PathBuf> {
+    let state_dir = dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .ok_or_else(|| eyre!("state directory not found"))?;
+    Ok(state_dir.join("upwork-tui"))
+}
+
+/// Sends tracing output to a daily log file under [`log_dir`] and to the
+/// returned buffer, and warnings to stderr too when `stderr` is set, for the
+/// subcommands that do not take over the terminal. `RUST_LOG` overrides the
+/// configured level.
+pub fn init(config: &LogConfig, stderr: bool) -> eyre::Result<LogBuffer> {
+    let dir = log_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let appender = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("upwork-tui")
+        .filename_suffix("log")
+        .max_log_files(config.max_files)
+        .build(dir)?;
+    let filter =
+        EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(&config.level))?;
+    let buffer = LogBuffer::default();
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(appender),
+        )
+        .with(buffer.clone())
+        .with(stderr.then(|| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(io::stderr)
+                .with_filter(LevelFilter::WARN)
+        }))
+        .try_init()?;
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_filters_by_level() {
+        let buffer = LogBuffer::default();
+        let subscriber = tracing_subscriber::registry().with(buffer.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(bytes = 42, "fetched feed");
+            tracing::warn!(query = "rust", "fetch failed");
+        });
+
+        let warnings = buffer.at_least(Level::WARN);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "fetch failed query=rust");
+        assert_eq!(warnings[0].target, module_path!());
+
+        let all = buffer.at_least(Level::TRACE);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].message, "fetched feed bytes=42");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(LogConfig::default().validate().is_ok());
+        assert!(LogConfig {
+            level: "upwork_tui=loud".to_string(),
+            ..LogConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -1,4 +1,110 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    net::SocketAddr,
+};
+
+use chrono::Utc;
+use clap::Parser;
+use color_eyre::eyre;
+use upwork_tui::{
+    app::App,
+    cli::{Cli, Command, ExportArgs, ReportArgs, SearchArgs, ServeArgs},
+    config::Config,
+    export::export,
+    fetch::Scheduler,
+    history::{History, HistoryQuery},
+    logging,
+    report::Report,
+    serve::serve,
+    store::JobStore,
+    tui, JobPost,
+};
+
 #[tokio::main]
-async fn main() {
-    todo!()
+async fn main() -> eyre::Result<()> {
+    tui::install_hooks()?;
+
+    let cli = Cli::parse();
+
+    let config_path = match &cli.config {
+        Some(path) => path.clone(),
+        None => Config::default_path()?,
+    };
+    let mut config = Config::load(&config_path)?;
+    config.apply_cli(&cli)?;
+    let logs = logging::init(&config.log, cli.command.is_some())?;
+
+    match cli.command {
+        Some(Command::Export(ref args)) => export_store(&config, &cli, args),
+        Some(Command::Serve(ref args)) => serve_feeds(config, args).await,
+        Some(Command::Search(ref args)) => search_history(args),
+        Some(Command::Report(ref args)) => report_history(&config, args),
+        None => App::new(config, config_path, cli, logs)?.run().await,
+    }
+}
+
+fn export_store(config: &Config, cli: &Cli, args: &ExportArgs) -> eyre::Result<()> {
+    let store = JobStore::load(&JobStore::default_path()?)?;
+
+    let jobs: Vec<&JobPost> = store
+        .visible(
+            args.view,
+            &config.filters,
+            cli.show_expired,
+            Utc::now().timestamp(),
+        )
+        .into_iter()
+        .map(|stored| &stored.post)
+        .collect();
+
+    match &args.output {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            export(&jobs, args.format, &mut writer)?;
+            writer.flush()?;
+        }
+        None => export(&jobs, args.format, io::stdout().lock())?,
+    }
+
+    Ok(())
+}
+
+fn search_history(args: &SearchArgs) -> eyre::Result<()> {
+    let history = History::open(&History::default_path()?)?;
+
+    let hits = history.search(&HistoryQuery {
+        text: args.query.join(" "),
+        skill: args.skill.clone(),
+        limit: args.limit,
+    })?;
+    let jobs: Vec<&JobPost> = hits.iter().map(|hit| &hit.post).collect();
+
+    export(&jobs, args.format, io::stdout().lock())
+}
+
+fn report_history(config: &Config, args: &ReportArgs) -> eyre::Result<()> {
+    let history = History::open(&History::default_path()?)?;
+
+    let until = Utc::now().timestamp();
+    let since = until - args.since.as_secs() as i64;
+    let jobs = history.posted_since(since)?;
+
+    let report = Report::build(&jobs, since, until, &config.currency, args.top);
+    report.write(args.format, io::stdout().lock())
+}
+
+async fn serve_feeds(config: Config, args: &ServeArgs) -> eyre::Result<()> {
+    let addr = SocketAddr::new(args.bind, args.port);
+
+    eprintln!("serving feeds on http://{}", addr);
+
+    serve(
+        addr,
+        config.subscriptions(),
+        config.filters,
+        config.poll_interval,
+        Scheduler::new(&config.fetch),
+    )
+    .await
 }
@@ -0,0 +1,347 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::StoredJob;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    #[default]
+    New,
+    Shortlisted,
+    Applied,
+    Interviewing,
+    Won,
+    Lost,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 6] = [
+        Stage::New,
+        Stage::Shortlisted,
+        Stage::Applied,
+        Stage::Interviewing,
+        Stage::Won,
+        Stage::Lost,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Stage::New => "New",
+            Stage::Shortlisted => "Shortlisted",
+            Stage::Applied => "Applied",
+            Stage::Interviewing => "Interviewing",
+            Stage::Won => "Won",
+            Stage::Lost => "Lost",
+        }
+    }
+
+    /// The next stage along the happy path; `Won` and `Lost` are final.
+    pub fn advance(self) -> Self {
+        match self {
+            Stage::New => Stage::Shortlisted,
+            Stage::Shortlisted => Stage::Applied,
+            Stage::Applied => Stage::Interviewing,
+            Stage::Interviewing | Stage::Won => Stage::Won,
+            Stage::Lost => Stage::Lost,
+        }
+    }
+
+    /// The previous stage along the happy path. A job can be lost from any
+    /// stage, so undoing `Lost` needs the history, see `Pipeline::retreat`.
+    pub fn retreat(self) -> Self {
+        match self {
+            Stage::New | Stage::Shortlisted => Stage::New,
+            Stage::Applied => Stage::Shortlisted,
+            Stage::Interviewing => Stage::Applied,
+            Stage::Won => Stage::Interviewing,
+            Stage::Lost => Stage::Lost,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub stage: Stage,
+    pub at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pipeline {
+    history: Vec<Transition>,
+}
+
+impl Pipeline {
+    pub fn stage(&self) -> Stage {
+        self.history
+            .last()
+            .map(|transition| transition.stage)
+            .unwrap_or_default()
+    }
+
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+
+    /// Records a transition, returning false when the job is already there.
+    pub fn move_to(&mut self, stage: Stage, now: i64) -> bool {
+        if stage == self.stage() {
+            return false;
+        }
+
+        self.history.push(Transition { stage, at: now });
+
+        true
+    }
+
+    /// The stage a job moves back to: a lost job returns to where it was
+    /// lost from, the others move one stage back.
+    pub fn retreat(&self) -> Stage {
+        match self.stage() {
+            Stage::Lost => self.furthest(),
+            stage => stage.retreat(),
+        }
+    }
+
+    /// Furthest stage reached on the way to winning, so a job lost after an
+    /// interview still counts as interviewed. Moving back a stage undoes the
+    /// ones after it, so this is the latest stage other than `Lost`.
+    pub fn furthest(&self) -> Stage {
+        self.history
+            .iter()
+            .rev()
+            .map(|transition| transition.stage)
+            .find(|stage| *stage != Stage::Lost)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Conversion {
+    pub applied: usize,
+    pub interviewing: usize,
+    pub won: usize,
+}
+
+impl Conversion {
+    fn record(&mut self, furthest: Stage) {
+        if furthest >= Stage::Applied {
+            self.applied += 1;
+        }
+        if furthest >= Stage::Interviewing {
+            self.interviewing += 1;
+        }
+        if furthest == Stage::Won {
+            self.won += 1;
+        }
+    }
+
+    /// Share of applications that were won, in percent.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.applied > 0).then(|| self.won as f64 * 100.0 / self.applied as f64)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ConversionSummary {
+    pub total: Conversion,
+    pub by_category: BTreeMap<String, Conversion>,
+    pub by_skill: BTreeMap<String, Conversion>,
+}
+
+pub fn conversions<'a>(jobs: impl IntoIterator<Item = &'a StoredJob>) -> ConversionSummary {
+    let mut summary = ConversionSummary::default();
+
+    for stored in jobs {
+        let furthest = stored.pipeline.furthest();
+        if furthest < Stage::Applied {
+            continue;
+        }
+
+        summary.total.record(furthest);
+
+        let category = stored
+            .post
+            .detail
+            .get("Category")
+            .unwrap_or(&stored.post.category);
+        summary
+            .by_category
+            .entry(category.clone())
+            .or_default()
+            .record(furthest);
+
+        let skills = stored.post.detail.get("Skills").map(String::as_str);
+        for skill in skills.unwrap_or_default().split(',').map(str::trim) {
+            if !skill.is_empty() {
+                summary
+                    .by_skill
+                    .entry(skill.to_owned())
+                    .or_default()
+                    .record(furthest);
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::JobStore;
+    use crate::JobPost;
+    use std::collections::HashMap;
+
+    const NOW: i64 = 1693880353;
+
+    fn job(id: &str, category: &str, skills: &str) -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Category".to_string(), category.to_string());
+        detail.insert("Skills".to_string(), skills.to_string());
+
+        JobPost {
+            title: format!("Title {}", id),
+            link: format!("https://www.upwork.com/jobs/Job_%7E{}", id),
+            detail,
+            posted_timestamp: NOW,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_move_to_records_transitions() {
+        let mut pipeline = Pipeline::default();
+
+        assert_eq!(pipeline.stage(), Stage::New);
+        assert!(pipeline.move_to(Stage::Shortlisted, NOW));
+        assert!(!pipeline.move_to(Stage::Shortlisted, NOW + 1));
+        assert!(pipeline.move_to(Stage::Applied, NOW + 60));
+        assert!(pipeline.move_to(Stage::Lost, NOW + 120));
+
+        assert_eq!(pipeline.stage(), Stage::Lost);
+        assert_eq!(pipeline.furthest(), Stage::Applied);
+        assert_eq!(
+            pipeline.history()[1],
+            Transition {
+                stage: Stage::Applied,
+                at: NOW + 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_retreat_from_lost_returns_to_previous_stage() {
+        let mut pipeline = Pipeline::default();
+        pipeline.move_to(Stage::Applied, NOW);
+        pipeline.move_to(Stage::Lost, NOW + 60);
+
+        assert_eq!(pipeline.retreat(), Stage::Applied);
+        pipeline.move_to(pipeline.retreat(), NOW + 120);
+        assert_eq!(pipeline.stage(), Stage::Applied);
+        assert_eq!(pipeline.furthest(), Stage::Applied);
+
+        for stage in [Stage::Interviewing, Stage::Won, Stage::Lost] {
+            pipeline.move_to(stage, NOW + 180);
+        }
+        assert_eq!(pipeline.retreat(), Stage::Won);
+        assert_eq!(Stage::Lost.retreat(), Stage::Lost);
+    }
+
+    #[test]
+    fn test_retreat_undoes_furthest() {
+        let mut pipeline = Pipeline::default();
+        let mut stage = Stage::New;
+
+        for at in 0..4 {
+            stage = stage.advance();
+            pipeline.move_to(stage, NOW + at);
+        }
+        assert_eq!(pipeline.furthest(), Stage::Won);
+
+        stage = stage.retreat().retreat();
+        pipeline.move_to(stage, NOW + 10);
+        assert_eq!(pipeline.furthest(), Stage::Applied);
+
+        pipeline.move_to(Stage::Lost, NOW + 20);
+        assert_eq!(pipeline.furthest(), Stage::Applied);
+        assert_eq!(pipeline.history().len(), 6);
+
+        let mut store = JobStore::default();
+        store.insert(vec![job("undone", "Design", "Figma")], NOW);
+        for stage in [
+            Stage::Applied,
+            Stage::Interviewing,
+            Stage::Won,
+            Stage::Interviewing,
+        ] {
+            store.set_stage("undone", stage, NOW);
+        }
+
+        assert_eq!(
+            conversions(store.iter()).total,
+            Conversion {
+                applied: 1,
+                interviewing: 1,
+                won: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_conversions_per_category_and_skill() {
+        let mut store = JobStore::default();
+        store.insert(
+            vec![
+                job("won", "Web Development", "Rust, Tokio"),
+                job("lost", "Web Development", "Rust"),
+                job("applied", "Design", "Figma"),
+                job("shortlisted", "Design", "Figma"),
+            ],
+            NOW,
+        );
+        for (id, stages) in [
+            (
+                "won",
+                &[Stage::Applied, Stage::Interviewing, Stage::Won][..],
+            ),
+            (
+                "lost",
+                &[Stage::Applied, Stage::Interviewing, Stage::Lost][..],
+            ),
+            ("applied", &[Stage::Applied][..]),
+            ("shortlisted", &[Stage::Shortlisted][..]),
+        ] {
+            for stage in stages {
+                store.set_stage(id, *stage, NOW);
+            }
+        }
+
+        let summary = conversions(store.iter());
+
+        assert_eq!(
+            summary.total,
+            Conversion {
+                applied: 3,
+                interviewing: 2,
+                won: 1
+            }
+        );
+        assert_eq!(
+            summary.by_category["Web Development"].win_rate(),
+            Some(50.0)
+        );
+        assert_eq!(summary.by_category["Design"].win_rate(), Some(0.0));
+        assert_eq!(summary.by_skill["Rust"].applied, 2);
+        assert_eq!(summary.by_skill["Tokio"].won, 1);
+        assert!(!summary.by_skill.contains_key(""));
+    }
+}
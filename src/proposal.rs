@@ -0,0 +1,172 @@
+use std::{env, fs, io::Write, process::Command};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use color_eyre::eyre::{self, bail, WrapErr};
+use serde::Deserialize;
+
+use crate::{template, JobPost};
+
+const DEFAULT_TEMPLATE: &str = "\
+Hi,
+
+I read your post \"{{title}}\" and I'd like to help. I work daily with {{skills}}.
+
+Budget: {{budget}}
+
+Best regards
+";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProposalConfig {
+    pub template: String,
+    /// Overrides `$VISUAL` and `$EDITOR`, e.g. `code --wait`
+    pub editor: Option<String>,
+}
+
+impl Default for ProposalConfig {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.to_string(),
+            editor: None,
+        }
+    }
+}
+
+impl ProposalConfig {
+    pub fn editor(&self) -> String {
+        self.editor
+            .clone()
+            .or_else(|| env::var("VISUAL").ok())
+            .or_else(|| env::var("EDITOR").ok())
+            .filter(|editor| !editor.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string())
+    }
+
+    pub fn initial_draft(&self, job: &JobPost) -> String {
+        template::render(&self.template, job, str::to_owned)
+    }
+}
+
+/// Opens `draft` in `editor` and returns the saved text. The terminal has to
+/// be handed over to the editor by the caller.
+pub fn edit(editor: &str, id: &str, draft: &str) -> eyre::Result<String> {
+    // Created exclusively under an unpredictable name, and removed on drop.
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("upwork-tui-draft-{}-", file_stem(id)))
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(draft.as_bytes())?;
+    let path = file.into_temp_path();
+
+    let mut words = editor.split_whitespace();
+    let Some(program) = words.next() else {
+        bail!("no editor configured");
+    };
+
+    let status = Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .wrap_err_with(|| format!("failed to run editor `{}`", editor));
+
+    let edited = fs::read_to_string(&path);
+    let _ = path.close();
+
+    let status = status?;
+    if !status.success() {
+        bail!("editor `{}` exited with {}", editor, status);
+    }
+
+    Ok(edited?)
+}
+
+/// Keeps the id readable in the file name without letting it add directories.
+fn file_stem(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .take(32)
+        .collect()
+}
+
+/// OSC 52 escape sequence that asks the terminal to put `text` on the
+/// clipboard, which also works over ssh.
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn job() -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Budget".to_string(), "$500".to_string());
+        detail.insert("Skills".to_string(), "Rust, Tokio".to_string());
+
+        JobPost {
+            title: "Build a crawler".to_string(),
+            link: "https://www.upwork.com/jobs/Crawler_%7E0118c283ded19e9ec0".to_string(),
+            category: "web_development".to_string(),
+            detail,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_initial_draft_fills_template() {
+        let draft = ProposalConfig::default().initial_draft(&job());
+
+        assert!(draft.contains("\"Build a crawler\""));
+        assert!(draft.contains("work daily with Rust, Tokio."));
+        assert!(draft.contains("Budget: $500"));
+    }
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_returns_saved_text() -> eyre::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let script = dir.path().join("editor.sh");
+        let opened = dir.path().join("opened");
+        fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho edited >> \"$1\"\necho \"$1\" > {}\n",
+                opened.display()
+            ),
+        )?;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+
+        let edited = edit(
+            &script.display().to_string(),
+            "https://remote.example.com/jobs/1042",
+            "draft\n",
+        );
+        let failed = edit("false", "02", "draft\n");
+
+        assert_eq!(edited?, "draft\nedited\n");
+        assert!(failed.is_err());
+
+        let opened = fs::read_to_string(opened)?;
+        let opened = std::path::Path::new(opened.trim_end());
+        assert_eq!(opened.parent(), Some(env::temp_dir().as_path()));
+        assert!(opened
+            .display()
+            .to_string()
+            .contains("upwork-tui-draft-https___remote_example_com_job"));
+        assert!(!opened.exists());
+
+        Ok(())
+    }
+}
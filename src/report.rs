@@ -0,0 +1,388 @@
+use std::{collections::BTreeMap, io::Write};
+
+use chrono::{DateTime, Days};
+use clap::ValueEnum;
+use color_eyre::eyre;
+use serde::Serialize;
+
+use crate::{
+    core_opt::price::{CurrencyConfig, Price},
+    skills::job_skills,
+    JobPost,
+};
+
+const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Distribution {
+    pub count: usize,
+    /// Amount at each of [`PERCENTILES`], in the preferred currency
+    pub percentiles: BTreeMap<String, f64>,
+}
+
+impl Distribution {
+    fn of(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+
+        let percentiles = match values.len() {
+            0 => BTreeMap::new(),
+            len => PERCENTILES
+                .iter()
+                .map(|p| {
+                    // Nearest rank: the smallest value with at least p% at or below it.
+                    let rank = (f64::from(*p) / 100.0 * len as f64).ceil() as usize;
+                    (format!("p{}", p), values[rank.clamp(1, len) - 1])
+                })
+                .collect(),
+        };
+
+        Self {
+            count: values.len(),
+            percentiles,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub since: i64,
+    pub until: i64,
+    pub currency: String,
+    pub total: usize,
+    /// Jobs posted on each UTC day from the first job on, including quiet days
+    pub per_day: BTreeMap<String, usize>,
+    /// Most common categories first
+    pub per_category: Vec<(String, usize)>,
+    /// Most common skills first
+    pub per_skill: Vec<(String, usize)>,
+    pub fixed: Distribution,
+    /// Top of each job's hourly range
+    pub hourly: Distribution,
+    /// Jobs whose price is missing or has no rate to the preferred currency
+    pub unpriced: usize,
+}
+
+impl Report {
+    /// Summarises jobs posted between `since` and `until`, keeping the `top`
+    /// most common categories and skills.
+    pub fn build<'a>(
+        jobs: impl IntoIterator<Item = &'a JobPost>,
+        since: i64,
+        until: i64,
+        currency: &CurrencyConfig,
+        top: usize,
+    ) -> Self {
+        let mut per_day = BTreeMap::new();
+        let mut total = 0;
+        let mut categories = BTreeMap::new();
+        let mut skills = BTreeMap::new();
+        let mut fixed = Vec::new();
+        let mut hourly = Vec::new();
+        let mut unpriced = 0;
+
+        let posted = jobs
+            .into_iter()
+            .filter(|job| (since..=until).contains(&job.posted_timestamp));
+        for job in posted {
+            total += 1;
+
+            if let Some(posted) = DateTime::from_timestamp(job.posted_timestamp, 0) {
+                *per_day.entry(posted.date_naive()).or_default() += 1;
+            }
+
+            let category = job.detail.get("Category").unwrap_or(&job.category);
+            *categories.entry(category.clone()).or_default() += 1;
+            for skill in job_skills(job) {
+                *skills.entry(skill.to_owned()).or_default() += 1;
+            }
+
+            match Price::from_job(job).and_then(|price| price.convert(currency)) {
+                Some(Price::Fixed(amount)) => fixed.push(amount.value),
+                Some(Price::Hourly { max, .. }) => hourly.push(max.value),
+                None => unpriced += 1,
+            }
+        }
+
+        // Fill in quiet days from the first job on, so gaps show in the trend.
+        let first = per_day.keys().next().copied();
+        if let (Some(mut day), Some(end)) = (first, DateTime::from_timestamp(until, 0)) {
+            while day <= end.date_naive() {
+                per_day.entry(day).or_insert(0);
+                day = day + Days::new(1);
+            }
+        }
+
+        Self {
+            since,
+            until,
+            currency: currency.preferred.to_uppercase(),
+            total,
+            per_day: per_day
+                .into_iter()
+                .map(|(day, count)| (day.to_string(), count))
+                .collect(),
+            per_category: most_common(categories, top),
+            per_skill: most_common(skills, top),
+            fixed: Distribution::of(fixed),
+            hourly: Distribution::of(hourly),
+            unpriced,
+        }
+    }
+
+    pub fn write<W: Write>(&self, format: ReportFormat, mut writer: W) -> eyre::Result<()> {
+        match format {
+            ReportFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, self)?;
+                writeln!(writer)?;
+            }
+            ReportFormat::Table => {
+                for (index, section) in self.sections().iter().enumerate() {
+                    if index > 0 {
+                        writeln!(writer)?;
+                    }
+                    section.write_table(&mut writer)?;
+                }
+            }
+            ReportFormat::Markdown => {
+                for (index, section) in self.sections().iter().enumerate() {
+                    if index > 0 {
+                        writeln!(writer)?;
+                    }
+                    section.write_markdown(&mut writer)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sections(&self) -> Vec<Section> {
+        let share = |count: usize| match self.total {
+            0 => "0%".to_string(),
+            total => format!("{:.0}%", count as f64 / total as f64 * 100.0),
+        };
+        let counts = |rows: &[(String, usize)]| {
+            rows.iter()
+                .map(|(name, count)| vec![name.clone(), count.to_string()])
+                .collect()
+        };
+
+        let mut headers = vec!["Type".to_string(), "Jobs".to_string(), "Share".to_string()];
+        headers.extend(PERCENTILES.iter().map(|p| format!("p{}", p)));
+        let prices = [("Fixed", &self.fixed), ("Hourly max", &self.hourly)]
+            .into_iter()
+            .map(|(label, distribution)| {
+                let mut row = vec![
+                    label.to_string(),
+                    distribution.count.to_string(),
+                    share(distribution.count),
+                ];
+                row.extend(PERCENTILES.iter().map(|p| {
+                    distribution
+                        .percentiles
+                        .get(&format!("p{}", p))
+                        .map(|value| format!("{:.0}", value))
+                        .unwrap_or_else(|| "-".to_string())
+                }));
+                row
+            })
+            .chain([vec![
+                "Unpriced".to_string(),
+                self.unpriced.to_string(),
+                share(self.unpriced),
+            ]])
+            .collect();
+
+        vec![
+            Section {
+                title: format!(
+                    "{} jobs from {} to {}",
+                    self.total,
+                    day(self.since),
+                    day(self.until)
+                ),
+                headers: vec!["Day".to_string(), "Jobs".to_string()],
+                rows: self
+                    .per_day
+                    .iter()
+                    .map(|(day, count)| vec![day.clone(), count.to_string()])
+                    .collect(),
+            },
+            Section {
+                title: "Categories".to_string(),
+                headers: vec!["Category".to_string(), "Jobs".to_string()],
+                rows: counts(&self.per_category),
+            },
+            Section {
+                title: "Skills".to_string(),
+                headers: vec!["Skill".to_string(), "Jobs".to_string()],
+                rows: counts(&self.per_skill),
+            },
+            Section {
+                title: format!("Prices ({})", self.currency),
+                headers,
+                rows: prices,
+            },
+        ]
+    }
+}
+
+struct Section {
+    title: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Section {
+    fn write_table<W: Write>(&self, writer: &mut W) -> eyre::Result<()> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let line = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    // Text in the first column, numbers right-aligned after it.
+                    if column == 0 {
+                        format!("{:<width$}", cell, width = width)
+                    } else {
+                        format!("{:>width$}", cell, width = width)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        writeln!(writer, "{}", self.title)?;
+        writeln!(writer, "{}", line(&self.headers))?;
+        for row in &self.rows {
+            writeln!(writer, "{}", line(row))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_markdown<W: Write>(&self, writer: &mut W) -> eyre::Result<()> {
+        writeln!(writer, "## {}", self.title)?;
+        writeln!(writer)?;
+        writeln!(writer, "| {} |", self.headers.join(" | "))?;
+        writeln!(
+            writer,
+            "|{}",
+            self.headers.iter().map(|_| " --- |").collect::<String>()
+        )?;
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            writeln!(writer, "| {} |", cells.join(" | "))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn most_common(counts: BTreeMap<String, usize>, top: usize) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(top);
+
+    counts
+}
+
+fn day(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|at| at.date_naive().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::parse_xml::parse_xml;
+    use std::fs;
+
+    fn report() -> eyre::Result<Report> {
+        let jobs = parse_xml(&fs::read("job.xml")?[..])?;
+        let until = jobs.iter().map(|job| job.posted_timestamp).max().unwrap();
+        let since = until - 7 * 24 * 60 * 60;
+
+        Ok(Report::build(
+            &jobs,
+            since,
+            until,
+            &CurrencyConfig::default(),
+            3,
+        ))
+    }
+
+    #[test]
+    fn test_distribution_percentiles() {
+        let distribution = Distribution::of((1..=10).rev().map(f64::from).collect());
+
+        assert_eq!(distribution.count, 10);
+        assert_eq!(distribution.percentiles["p10"], 1.0);
+        assert_eq!(distribution.percentiles["p50"], 5.0);
+        assert_eq!(distribution.percentiles["p90"], 9.0);
+        assert!(Distribution::of(Vec::new()).percentiles.is_empty());
+    }
+
+    #[test]
+    fn test_report_from_fixture() -> eyre::Result<()> {
+        let report = report()?;
+
+        assert_eq!(report.total, 30);
+        assert_eq!(report.per_day.len(), 6);
+        assert_eq!(report.per_day.values().sum::<usize>(), 30);
+        assert_eq!(report.per_day["2024-04-29"], 29);
+        assert_eq!(report.per_day["2024-04-24"], 1);
+        assert_eq!(report.per_day["2024-04-26"], 0);
+        assert_eq!(report.per_category.len(), 3);
+        assert_eq!(report.per_skill[0], ("Graphic Design".to_string(), 16));
+        assert_eq!(
+            report.fixed.count + report.hourly.count + report.unpriced,
+            30
+        );
+        assert_eq!(report.fixed.percentiles["p90"], 1000.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_formats() -> eyre::Result<()> {
+        let report = report()?;
+        let render = |format| -> eyre::Result<String> {
+            let mut out = Vec::new();
+            report.write(format, &mut out)?;
+            Ok(String::from_utf8(out)?)
+        };
+
+        let table = render(ReportFormat::Table)?;
+        assert!(table.starts_with(
+            "30 jobs from 2024-04-22 to 2024-04-29\nDay         Jobs\n2024-04-24     1\n"
+        ));
+        assert!(table.contains("\nGraphic Design"));
+
+        let markdown = render(ReportFormat::Markdown)?;
+        assert!(markdown.contains("## Prices (USD)\n\n| Type | Jobs | Share | p10 |"));
+
+        let json: serde_json::Value = serde_json::from_str(&render(ReportFormat::Json)?)?;
+        assert_eq!(json["total"], 30);
+        assert_eq!(json["per_skill"][0][0], "Graphic Design");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+use std::time::Duration;
+
+use color_eyre::eyre::{self, bail};
+use serde::Deserialize;
+
+use crate::{
+    config::Config,
+    core_opt::price::{CurrencyConfig, Price},
+    skills::SkillProfile,
+    JobPost,
+};
+
+const DEFAULT_AGE_WINDOW: Duration = Duration::from_secs(48 * 60 * 60);
+
+/// One input to a job's score. Implement it to plug extra signals into a
+/// [`Scorer`].
+pub trait Factor: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// From 0 for the least valuable job to 1 for the most.
+    fn score(&self, job: &JobPost, now: i64) -> f64;
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Weights {
+    pub budget: f64,
+    pub skills: f64,
+    pub age: f64,
+    pub country: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            budget: 1.0,
+            skills: 2.0,
+            age: 1.0,
+            country: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+    /// Rank the list by score instead of newest first
+    pub rank: bool,
+    pub weights: Weights,
+    /// Fixed budget, in the preferred currency, that earns the full budget score
+    pub fixed_target: f64,
+    /// Hourly maximum, in the preferred currency, that earns the full budget score
+    pub hourly_target: f64,
+    /// Age at which a job's age score drops to zero
+    #[serde(with = "humantime_serde")]
+    pub age_window: Duration,
+    /// Client countries that earn the full country score; all do when empty
+    pub preferred_countries: Vec<String>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            rank: false,
+            weights: Weights::default(),
+            fixed_target: 1000.0,
+            hourly_target: 50.0,
+            age_window: DEFAULT_AGE_WINDOW,
+            preferred_countries: Vec::new(),
+        }
+    }
+}
+
+impl ScoringConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        let Weights {
+            budget,
+            skills,
+            age,
+            country,
+        } = self.weights;
+
+        for (name, weight) in [
+            ("budget", budget),
+            ("skills", skills),
+            ("age", age),
+            ("country", country),
+        ] {
+            if !weight.is_finite() || weight < 0.0 {
+                bail!("weights.{}: must be zero or more", name);
+            }
+        }
+
+        let positive = |target: f64| target.is_finite() && target > 0.0;
+        if !positive(self.fixed_target) || !positive(self.hourly_target) {
+            bail!("fixed_target and hourly_target must be positive");
+        }
+
+        if self.age_window.is_zero() {
+            bail!("age_window: must not be zero");
+        }
+
+        Ok(())
+    }
+}
+
+pub struct BudgetFactor {
+    pub currency: CurrencyConfig,
+    pub fixed_target: f64,
+    pub hourly_target: f64,
+}
+
+impl Factor for BudgetFactor {
+    fn name(&self) -> &'static str {
+        "budget"
+    }
+
+    fn score(&self, job: &JobPost, _now: i64) -> f64 {
+        let ratio = match Price::from_job(job).and_then(|price| price.convert(&self.currency)) {
+            Some(Price::Fixed(amount)) => amount.value / self.fixed_target,
+            Some(Price::Hourly { max, .. }) => max.value / self.hourly_target,
+            None => 0.0,
+        };
+
+        ratio.clamp(0.0, 1.0)
+    }
+}
+
+pub struct SkillsFactor {
+    pub profile: SkillProfile,
+}
+
+impl Factor for SkillsFactor {
+    fn name(&self) -> &'static str {
+        "skills"
+    }
+
+    fn score(&self, job: &JobPost, _now: i64) -> f64 {
+        self.profile.matching(job).ratio()
+    }
+}
+
+pub struct AgeFactor {
+    pub window: Duration,
+}
+
+impl Factor for AgeFactor {
+    fn name(&self) -> &'static str {
+        "age"
+    }
+
+    fn score(&self, job: &JobPost, now: i64) -> f64 {
+        let age = (now - job.posted_timestamp).max(0) as f64;
+
+        (1.0 - age / self.window.as_secs_f64()).clamp(0.0, 1.0)
+    }
+}
+
+pub struct CountryFactor {
+    pub preferred: Vec<String>,
+}
+
+impl Factor for CountryFactor {
+    fn name(&self) -> &'static str {
+        "country"
+    }
+
+    fn score(&self, job: &JobPost, _now: i64) -> f64 {
+        if self.preferred.is_empty() {
+            return 1.0;
+        }
+
+        let preferred = job.country.as_ref().is_some_and(|country| {
+            self.preferred
+                .iter()
+                .any(|preferred| preferred.eq_ignore_ascii_case(country))
+        });
+
+        if preferred {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub name: &'static str,
+    pub value: f64,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// Weighted average of the parts, from 0 to 100
+    pub total: f64,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Default)]
+pub struct Scorer {
+    factors: Vec<(Box<dyn Factor>, f64)>,
+}
+
+impl Scorer {
+    pub fn from_config(config: &Config) -> Self {
+        let scoring = &config.scoring;
+        let weights = &scoring.weights;
+
+        Self::default()
+            .with(
+                BudgetFactor {
+                    currency: config.currency.clone(),
+                    fixed_target: scoring.fixed_target,
+                    hourly_target: scoring.hourly_target,
+                },
+                weights.budget,
+            )
+            .with(
+                SkillsFactor {
+                    profile: config.skills.clone(),
+                },
+                if config.skills.is_empty() {
+                    0.0
+                } else {
+                    weights.skills
+                },
+            )
+            .with(
+                AgeFactor {
+                    window: scoring.age_window,
+                },
+                weights.age,
+            )
+            .with(
+                CountryFactor {
+                    preferred: scoring.preferred_countries.clone(),
+                },
+                weights.country,
+            )
+    }
+
+    /// Adds a factor; factors with a zero weight are left out.
+    pub fn with(mut self, factor: impl Factor + 'static, weight: f64) -> Self {
+        if weight > 0.0 {
+            self.factors.push((Box::new(factor), weight));
+        }
+
+        self
+    }
+
+    pub fn score(&self, job: &JobPost, now: i64) -> Score {
+        let parts: Vec<Part> = self
+            .factors
+            .iter()
+            .map(|(factor, weight)| Part {
+                name: factor.name(),
+                value: factor.score(job, now),
+                weight: *weight,
+            })
+            .collect();
+
+        let weights: f64 = parts.iter().map(|part| part.weight).sum();
+        let weighted = parts
+            .iter()
+            .fold(0.0, |sum, part| sum + part.value * part.weight);
+        let total = if weights > 0.0 {
+            weighted / weights * 100.0
+        } else {
+            0.0
+        };
+
+        Score { total, parts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::parse_xml::parse_xml;
+    use std::fs;
+
+    fn fixture() -> eyre::Result<Vec<JobPost>> {
+        parse_xml(&fs::read("job.xml")?[..])
+    }
+
+    fn config(toml: &str) -> eyre::Result<Config> {
+        Config::parse(toml)
+    }
+
+    #[test]
+    fn test_score_breakdown_on_fixture() -> eyre::Result<()> {
+        let jobs = fixture()?;
+        let graphic_designer = jobs
+            .iter()
+            .find(|job| job.title.starts_with("Graphic Designer - "))
+            .unwrap();
+
+        let scorer = Scorer::from_config(&config(
+            r#"
+            [skills]
+            "Graphic Design" = 5
+            "Adobe Photoshop" = 3
+            "#,
+        )?);
+        let score = scorer.score(graphic_designer, graphic_designer.posted_timestamp);
+
+        let value = |name| {
+            score
+                .parts
+                .iter()
+                .find(|part| part.name == name)
+                .map(|part| part.value)
+        };
+        assert_eq!(value("budget"), Some(10.0 / 50.0));
+        assert_eq!(value("skills"), Some((1.0 + 0.6) / 4.0));
+        assert_eq!(value("age"), Some(1.0));
+        assert_eq!(value("country"), Some(1.0));
+        assert!((score.total - (0.2 + 2.0 * 0.4 + 1.0 + 0.5) / 4.5 * 100.0).abs() < 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_weights_change_ranking() -> eyre::Result<()> {
+        let jobs = fixture()?;
+        let now = jobs.iter().map(|job| job.posted_timestamp).max().unwrap();
+        let ranked = |config: &Config| {
+            let scorer = Scorer::from_config(config);
+            let mut scored: Vec<(f64, &str)> = jobs
+                .iter()
+                .map(|job| (scorer.score(job, now).total, job.title.as_str()))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            scored
+        };
+
+        let budget_only = ranked(&config(
+            "[scoring.weights]\nskills = 0.0\nage = 0.0\ncountry = 0.0",
+        )?);
+        let mut top: Vec<&str> = budget_only[..3].iter().map(|(_, title)| *title).collect();
+        top.sort();
+        assert_eq!(
+            top,
+            vec![
+                "Behance/dribbble designer [3 designers needed asap] - Upwork",
+                "Experienced 3D Interior Designer for House Project - Upwork",
+                "I need a designer to make animated graphics that are colorful and animated - Upwork",
+            ]
+        );
+        assert!(budget_only[3].0 < 100.0);
+
+        let from_india = ranked(&config(
+            "[scoring]\npreferred_countries = [\"India\"]\n[scoring.weights]\nskills = 0.0\nage = 0.0",
+        )?);
+        assert_eq!(
+            from_india[0].1,
+            "Behance/dribbble designer [3 designers needed asap] - Upwork"
+        );
+        assert!(from_india[1].0 < from_india[0].0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_scoring_config() {
+        assert!(config("[scoring.weights]\nbudget = -1.0").is_err());
+        assert!(config("[scoring]\nhourly_target = 0.0").is_err());
+        assert!(config("[scoring]\nfixed_target = nan").is_err());
+        assert!(config("[scoring]\nhourly_target = inf").is_err());
+        assert!(config("[scoring]\nage_window = \"0s\"").is_err());
+        assert!(config("[scoring.weights]\nsalary = 1.0").is_err());
+        assert_eq!(
+            Scorer::default().score(&fixture().unwrap()[0], 0).total,
+            0.0
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use atom_syndication::{Category as AtomCategory, Entry, Feed, FixedDateTime, Link, Text};
+use axum::{
+    extract::{Path, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre;
+use futures::future::join_all;
+use quick_xml::escape::escape;
+use reqwest::Url;
+use rss::{Category, ChannelBuilder, GuidBuilder, ItemBuilder};
+use tokio::net::TcpListener;
+
+use crate::{
+    core_opt::{filter::FilterRules, price_label, source::Subscription},
+    fetch::Scheduler,
+    JobPost,
+};
+
+pub type Feeds = Arc<RwLock<BTreeMap<String, Vec<JobPost>>>>;
+
+#[derive(Clone)]
+struct FeedState {
+    feeds: Feeds,
+    rules: Arc<FilterRules>,
+}
+
+pub fn router(feeds: Feeds, rules: FilterRules) -> Router {
+    let state = FeedState {
+        feeds,
+        rules: Arc::new(rules),
+    };
+
+    Router::new()
+        .route("/", get(index))
+        .route("/feeds/{search}/rss", get(rss_handler))
+        .route("/feeds/{search}/atom", get(atom_handler))
+        .with_state(state)
+}
+
+pub async fn serve(
+    addr: SocketAddr,
+    subscriptions: Vec<Subscription>,
+    rules: FilterRules,
+    poll_interval: Duration,
+    scheduler: Scheduler,
+) -> eyre::Result<()> {
+    let feeds: Feeds = Arc::new(RwLock::new(
+        subscriptions
+            .iter()
+            .map(|subscription| (subscription.name().to_owned(), Vec::new()))
+            .collect(),
+    ));
+    let poll_feeds = feeds.clone();
+
+    tokio::spawn(async move {
+        let mut poll = tokio::time::interval(poll_interval);
+
+        loop {
+            poll.tick().await;
+
+            join_all(subscriptions.iter().map(|subscription| {
+                let (scheduler, poll_feeds) = (&scheduler, &poll_feeds);
+                let search = subscription.name();
+
+                async move {
+                    match scheduler.run(subscription.fetch()).await {
+                        Ok(jobs) => {
+                            tracing::info!(search, jobs = jobs.len(), "refreshed served feed");
+                            poll_feeds
+                                .write()
+                                .expect("feed lock poisoned")
+                                .insert(search.to_owned(), jobs);
+                        }
+                        Err(err) => tracing::warn!(search, error = %format_args!("{:#}", err), "fetch failed"),
+                    }
+                }
+            }))
+            .await;
+        }
+    });
+
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router(feeds, rules)).await?;
+
+    Ok(())
+}
+
+async fn index(State(state): State<FeedState>) -> String {
+    let feeds = state.feeds.read().expect("feed lock poisoned");
+
+    feeds
+        .keys()
+        .map(|search| {
+            format!(
+                "{}\n{}\n",
+                feed_path(search, "rss"),
+                feed_path(search, "atom")
+            )
+        })
+        .collect()
+}
+
+async fn rss_handler(State(state): State<FeedState>, Path(search): Path<String>) -> Response {
+    match filtered_jobs(&state, &search) {
+        Some(jobs) => (
+            [(CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+            rss_feed(&search, &jobs),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn atom_handler(State(state): State<FeedState>, Path(search): Path<String>) -> Response {
+    match filtered_jobs(&state, &search) {
+        Some(jobs) => (
+            [(CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+            atom_feed(&search, &jobs),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn filtered_jobs(state: &FeedState, search: &str) -> Option<Vec<JobPost>> {
+    let feeds = state.feeds.read().expect("feed lock poisoned");
+    let now = Utc::now().timestamp();
+
+    feeds.get(search).map(|jobs| {
+        jobs.iter()
+            .filter(|job| state.rules.matches(job, now))
+            .cloned()
+            .collect()
+    })
+}
+
+pub fn rss_feed(search: &str, jobs: &[JobPost]) -> String {
+    let items: Vec<rss::Item> = jobs
+        .iter()
+        .map(|job| {
+            ItemBuilder::default()
+                .title(Some(job.title.clone()))
+                .link(Some(job.link.clone()))
+                .guid(Some(
+                    GuidBuilder::default()
+                        .value(job.link.clone())
+                        .permalink(true)
+                        .build(),
+                ))
+                .pub_date(Some(to_fixed(job.posted_timestamp).to_rfc2822()))
+                .categories(vec![Category {
+                    name: job.category.clone(),
+                    domain: None,
+                }])
+                .description(Some(summary(job)))
+                .build()
+        })
+        .collect();
+
+    ChannelBuilder::default()
+        .title(format!("{} | upwork-tui", search))
+        .link(feed_path(search, "rss"))
+        .description(format!("Filtered jobs for \"{}\"", search))
+        .items(items)
+        .build()
+        .to_string()
+}
+
+pub fn atom_feed(search: &str, jobs: &[JobPost]) -> String {
+    let entries: Vec<Entry> = jobs
+        .iter()
+        .map(|job| Entry {
+            id: job.link.clone(),
+            title: Text::plain(job.title.clone()),
+            updated: to_fixed(job.posted_timestamp),
+            published: Some(to_fixed(job.posted_timestamp)),
+            links: vec![Link {
+                href: job.link.clone(),
+                ..Default::default()
+            }],
+            categories: vec![AtomCategory {
+                term: job.category.clone(),
+                ..Default::default()
+            }],
+            summary: Some(Text::html(summary(job))),
+            ..Default::default()
+        })
+        .collect();
+
+    let updated = jobs
+        .iter()
+        .map(|job| job.posted_timestamp)
+        .max()
+        .unwrap_or_default();
+
+    Feed {
+        id: format!("urn:upwork-tui:{}", search),
+        title: Text::plain(format!("{} | upwork-tui", search)),
+        updated: to_fixed(updated),
+        entries,
+        ..Default::default()
+    }
+    .to_string()
+}
+
+/// The job as HTML for feed readers. Every value comes from the fetched feed,
+/// so it is escaped before it goes into the markup.
+fn summary(job: &JobPost) -> String {
+    let detail = |key: &str| escape(job.detail.get(key).map_or("", String::as_str)).into_owned();
+
+    format!(
+        "<p>{}</p><p><b>Price</b>: {}<br /><b>Skills</b>: {}<br /><b>Country</b>: {}</p>",
+        detail("details"),
+        escape(price_label(job)),
+        detail("Skills"),
+        escape(job.country.as_deref().unwrap_or_default())
+    )
+}
+
+/// Where a feed is served, with the search or feed name percent-encoded.
+fn feed_path(search: &str, format: &str) -> String {
+    let mut url = Url::parse("http://localhost/feeds").expect("base url is valid");
+    url.path_segments_mut()
+        .expect("base url has a path")
+        .extend([search, format]);
+
+    url.path().to_owned()
+}
+
+fn to_fixed(timestamp: i64) -> FixedDateTime {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .fixed_offset()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_opt::source::{parse_feed, SourceKind};
+    use std::collections::HashMap;
+
+    fn job(id: &str, timestamp: i64) -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Budget".to_string(), "$500".to_string());
+        detail.insert("details".to_string(), "Need a logo".to_string());
+
+        JobPost {
+            title: format!("Logo {}", id),
+            link: format!("https://www.upwork.com/jobs/Logo_%7E{}", id),
+            category: "graphic_design".to_string(),
+            detail,
+            posted_on: DateTime::from_timestamp(timestamp, 0).unwrap().to_rfc2822(),
+            posted_timestamp: timestamp,
+            ..Default::default()
+        }
+    }
+
+    async fn spawn_server(feeds: Feeds) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            axum::serve(listener, router(feeds, FilterRules::default()))
+                .await
+                .unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn feeds_with(search: &str, jobs: Vec<JobPost>) -> Feeds {
+        let feeds: Feeds = Arc::default();
+        feeds.write().unwrap().insert(search.to_string(), jobs);
+        feeds
+    }
+
+    #[tokio::test]
+    async fn test_serve_rss_applies_filter_rules() -> eyre::Result<()> {
+        let now = Utc::now().timestamp();
+        let feeds = feeds_with(
+            "logo design",
+            vec![job("fresh", now), job("stale", now - 7 * 86400)],
+        );
+        let base = spawn_server(feeds).await;
+
+        let body = reqwest::get(format!("{}/feeds/logo%20design/rss", base))
+            .await?
+            .bytes()
+            .await?;
+        let channel = rss::Channel::read_from(&body[..])?;
+
+        assert_eq!(channel.items.len(), 1);
+        assert_eq!(channel.items[0].title(), Some("Logo fresh"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serve_rss_dates_and_links() -> eyre::Result<()> {
+        let atom = std::fs::read("tests/fixtures/sources/freelance_atom.xml")?;
+        let mut jobs = parse_feed(&atom[..], SourceKind::Generic)?;
+        let now = Utc::now().timestamp();
+        for job in &mut jobs {
+            job.posted_timestamp = now - 60;
+        }
+        let base = spawn_server(feeds_with("gigs / logo #1", jobs)).await;
+
+        let index = reqwest::get(&base).await?.text().await?;
+        assert_eq!(
+            index,
+            "/feeds/gigs%20%2F%20logo%20%231/rss\n/feeds/gigs%20%2F%20logo%20%231/atom\n"
+        );
+
+        let body = reqwest::get(format!("{}{}", base, index.lines().next().unwrap()))
+            .await?
+            .bytes()
+            .await?;
+        let channel = rss::Channel::read_from(&body[..])?;
+
+        assert_eq!(channel.link(), "/feeds/gigs%20%2F%20logo%20%231/rss");
+        assert_eq!(channel.items.len(), 2);
+        for item in &channel.items {
+            let date = DateTime::parse_from_rfc2822(item.pub_date().unwrap())?;
+            assert_eq!(date.timestamp(), now - 60);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serve_atom() -> eyre::Result<()> {
+        let now = Utc::now().timestamp();
+        let base = spawn_server(feeds_with("logo", vec![job("fresh", now)])).await;
+
+        let response = reqwest::get(format!("{}/feeds/logo/atom", base)).await?;
+        assert_eq!(
+            response.headers()[CONTENT_TYPE.as_str()],
+            "application/atom+xml; charset=utf-8"
+        );
+
+        let body = response.bytes().await?;
+        let feed = Feed::read_from(&body[..])?;
+
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(
+            feed.entries[0].links[0].href,
+            "https://www.upwork.com/jobs/Logo_%7Efresh"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_escapes_markup() {
+        let mut job = job("xss", 0);
+        job.detail.insert(
+            "details".to_string(),
+            "<script>alert(1)</script><img src=x onerror=\"alert(1)\">".to_string(),
+        );
+        job.detail
+            .insert("Skills".to_string(), "C & <b>Rust</b>".to_string());
+        job.country = Some("<i>Nowhere</i>".to_string());
+
+        let summary = summary(&job);
+
+        assert!(summary.starts_with(
+            "<p>&lt;script&gt;alert(1)&lt;/script&gt;&lt;img src=x onerror=&quot;alert(1)&quot;&gt;</p>"
+        ));
+        assert!(summary.contains("<b>Skills</b>: C &amp; &lt;b&gt;Rust&lt;/b&gt;<br />"));
+        assert!(summary.ends_with("<b>Country</b>: &lt;i&gt;Nowhere&lt;/i&gt;</p>"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_unknown_search() -> eyre::Result<()> {
+        let base = spawn_server(Arc::default()).await;
+
+        let response = reqwest::get(format!("{}/feeds/missing/rss", base)).await?;
+
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
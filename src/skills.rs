@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::{self, bail};
+use serde::Deserialize;
+
+use crate::JobPost;
+
+const MAX_LEVEL: u8 = 5;
+
+/// The team's skills with a level from 1 to 5, e.g. `Rust = 5`.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(transparent)]
+pub struct SkillProfile {
+    levels: BTreeMap<String, u8>,
+}
+
+impl SkillProfile {
+    pub fn validate(&self) -> eyre::Result<()> {
+        for (skill, level) in &self.levels {
+            if !(1..=MAX_LEVEL).contains(level) {
+                bail!("{}: level must be between 1 and {}", skill, MAX_LEVEL);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    pub fn level(&self, skill: &str) -> Option<u8> {
+        self.levels
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(skill))
+            .map(|(_, level)| *level)
+    }
+
+    pub fn matching<'a>(&self, job: &'a JobPost) -> SkillMatch<'a> {
+        let mut matched = SkillMatch::default();
+
+        for skill in job_skills(job) {
+            match self.level(skill) {
+                Some(level) => matched.known.push((skill, level)),
+                None => matched.missing.push(skill),
+            }
+        }
+
+        matched
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SkillMatch<'a> {
+    /// Job skills the team has, with the team's level
+    pub known: Vec<(&'a str, u8)>,
+    pub missing: Vec<&'a str>,
+}
+
+impl SkillMatch<'_> {
+    /// Level-weighted share of the job's skills, from 0 to 1: a level 5 skill
+    /// counts fully and a level 1 skill a fifth.
+    pub fn ratio(&self) -> f64 {
+        let total = self.known.len() + self.missing.len();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let weighted = self.known.iter().fold(0.0, |sum, (_, level)| {
+            sum + f64::from(*level) / f64::from(MAX_LEVEL)
+        });
+
+        weighted / total as f64
+    }
+}
+
+/// The comma-separated `Skills` captured by `get_detail`.
+pub fn job_skills(job: &JobPost) -> Vec<&str> {
+    job.detail
+        .get("Skills")
+        .map(|skills| {
+            skills
+                .split(',')
+                .map(str::trim)
+                .filter(|skill| !skill.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn job(skills: &str) -> JobPost {
+        JobPost {
+            detail: HashMap::from([("Skills".to_string(), skills.to_string())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_level_weighted_match() -> eyre::Result<()> {
+        let profile: SkillProfile = toml::from_str("Rust = 5\nTokio = 4\nReact = 2")?;
+
+        let rust_job = job("rust, React, Tokio, CSS");
+        let matched = profile.matching(&rust_job);
+        assert_eq!(matched.known, vec![("rust", 5), ("React", 2), ("Tokio", 4)]);
+        assert_eq!(matched.missing, vec!["CSS"]);
+        assert!((matched.ratio() - (1.0 + 0.4 + 0.8) / 4.0).abs() < 1e-9);
+
+        assert_eq!(profile.matching(&job("")).ratio(), 0.0);
+        assert_eq!(job_skills(&job(" Rust ,, Tokio ")), vec!["Rust", "Tokio"]);
+
+        let invalid: SkillProfile = toml::from_str("Rust = 9")?;
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
+}
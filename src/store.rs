@@ -0,0 +1,507 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use color_eyre::eyre::{self, eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core_opt::filter::FilterRules,
+    pipeline::{Pipeline, Stage},
+    JobPost,
+};
+
+const DEFAULT_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StoredJob {
+    pub post: JobPost,
+    pub first_seen: i64,
+    #[serde(default)]
+    pub read: bool,
+    #[serde(default)]
+    pub starred: bool,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Pipeline::is_empty")]
+    pub pipeline: Pipeline,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<String>,
+}
+
+impl StoredJob {
+    pub fn new(post: JobPost, first_seen: i64) -> Self {
+        Self {
+            post,
+            first_seen,
+            read: false,
+            starred: false,
+            hidden: false,
+            note: None,
+            pipeline: Pipeline::default(),
+            draft: None,
+        }
+    }
+
+    fn is_annotated(&self) -> bool {
+        self.starred
+            || self.hidden
+            || self.note.is_some()
+            || self.draft.is_some()
+            || !self.pipeline.is_empty()
+    }
+}
+
+/// Which slice of the store the list shows. Only `All` applies the age
+/// window, so starred and annotated jobs stay reachable once they expire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum View {
+    #[default]
+    All,
+    Starred,
+    Hidden,
+    Notes,
+}
+
+impl View {
+    pub fn next(self) -> Self {
+        match self {
+            View::All => View::Starred,
+            View::Starred => View::Notes,
+            View::Notes => View::Hidden,
+            View::Hidden => View::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            View::All => "all",
+            View::Starred => "starred",
+            View::Hidden => "hidden",
+            View::Notes => "has notes",
+        }
+    }
+
+    fn includes(self, stored: &StoredJob) -> bool {
+        match self {
+            View::All => !stored.hidden,
+            View::Starred => stored.starred,
+            View::Hidden => stored.hidden,
+            View::Notes => stored.note.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Retention {
+    #[serde(with = "humantime_serde")]
+    pub max_age: Duration,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_age: DEFAULT_RETENTION,
+        }
+    }
+}
+
+impl Retention {
+    /// Whether `post` is young enough to be kept.
+    pub fn keeps(&self, post: &JobPost, now: i64) -> bool {
+        now - post.posted_timestamp <= self.max_age.as_secs() as i64
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobStore {
+    jobs: BTreeMap<String, StoredJob>,
+}
+
+impl JobStore {
+    pub fn default_path() -> eyre::Result<PathBuf> {
+        let data_dir = dirs::data_dir().ok_or_else(|| eyre!("data directory not found"))?;
+        Ok(data_dir.join("upwork-tui").join("jobs.json"))
+    }
+
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read(path)?;
+        let store = serde_json::from_slice(&data)?;
+
+        Ok(store)
+    }
+
+    pub fn save(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let data = serde_json::to_vec(self)?;
+        fs::write(path, data)?;
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&StoredJob> {
+        self.jobs.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StoredJob> {
+        self.jobs.values()
+    }
+
+    pub fn mark_read(&mut self, id: &str) {
+        if let Some(stored) = self.jobs.get_mut(id) {
+            stored.read = true;
+        }
+    }
+
+    pub fn toggle_starred(&mut self, id: &str) -> Option<bool> {
+        let stored = self.jobs.get_mut(id)?;
+        stored.starred = !stored.starred;
+        Some(stored.starred)
+    }
+
+    pub fn toggle_hidden(&mut self, id: &str) -> Option<bool> {
+        let stored = self.jobs.get_mut(id)?;
+        stored.hidden = !stored.hidden;
+        Some(stored.hidden)
+    }
+
+    /// Sets or, for a blank note, clears the note on a job.
+    pub fn set_note(&mut self, id: &str, note: &str) -> bool {
+        let Some(stored) = self.jobs.get_mut(id) else {
+            return false;
+        };
+
+        let note = note.trim();
+        stored.note = (!note.is_empty()).then(|| note.to_owned());
+
+        true
+    }
+
+    pub fn set_draft(&mut self, id: &str, draft: String) -> bool {
+        let Some(stored) = self.jobs.get_mut(id) else {
+            return false;
+        };
+
+        stored.draft = (!draft.trim().is_empty()).then_some(draft);
+
+        true
+    }
+
+    /// Moves a job to `stage`, returning whether it was found and moved.
+    pub fn set_stage(&mut self, id: &str, stage: Stage, now: i64) -> bool {
+        self.jobs
+            .get_mut(id)
+            .is_some_and(|stored| stored.pipeline.move_to(stage, now))
+    }
+
+    /// Jobs in a board column, most recently moved first. The `New` column
+    /// only holds starred jobs, the rest of the feed is not on the board.
+    pub fn in_stage(&self, stage: Stage) -> Vec<&StoredJob> {
+        let mut jobs: Vec<&StoredJob> = self
+            .jobs
+            .values()
+            .filter(|stored| !stored.hidden && stored.pipeline.stage() == stage)
+            .filter(|stored| stage != Stage::New || stored.starred)
+            .collect();
+
+        jobs.sort_by_key(|stored| {
+            let moved = stored
+                .pipeline
+                .history()
+                .last()
+                .map(|transition| transition.at);
+            std::cmp::Reverse(moved.unwrap_or(stored.post.posted_timestamp))
+        });
+
+        jobs
+    }
+
+    pub fn insert(&mut self, posts: Vec<JobPost>, now: i64) -> Vec<String> {
+        let mut new_ids = Vec::new();
+
+        for post in posts {
+            let id = post.id().to_owned();
+
+            match self.jobs.get_mut(&id) {
+                Some(stored) => stored.post = post,
+                None => {
+                    self.jobs.insert(id.clone(), StoredJob::new(post, now));
+                    new_ids.push(id);
+                }
+            }
+        }
+
+        new_ids
+    }
+
+    /// Like `insert`, but jobs not stored yet that are already past the
+    /// retention window are skipped. Otherwise they would be pruned and come
+    /// back as new on every fetch.
+    pub fn insert_retained(
+        &mut self,
+        posts: Vec<JobPost>,
+        retention: &Retention,
+        now: i64,
+    ) -> Vec<String> {
+        let posts = posts
+            .into_iter()
+            .filter(|post| self.jobs.contains_key(post.id()) || retention.keeps(post, now))
+            .collect();
+
+        self.insert(posts, now)
+    }
+
+    /// Drops jobs older than the retention window, except starred, hidden or
+    /// noted ones. Hidden jobs are kept so a repost of the same id stays hidden.
+    pub fn prune(&mut self, retention: &Retention, now: i64) -> usize {
+        let before = self.jobs.len();
+
+        self.jobs
+            .retain(|_, stored| stored.is_annotated() || retention.keeps(&stored.post, now));
+
+        before - self.jobs.len()
+    }
+
+    pub fn visible(
+        &self,
+        view: View,
+        rules: &FilterRules,
+        show_expired: bool,
+        now: i64,
+    ) -> Vec<&StoredJob> {
+        let show_expired = show_expired || view != View::All;
+
+        let mut jobs: Vec<&StoredJob> = self
+            .jobs
+            .values()
+            .filter(|stored| view.includes(stored))
+            .filter(|stored| view != View::All || rules.country_allowed(&stored.post))
+            .filter(|stored| show_expired || !rules.is_expired(&stored.post, now))
+            .collect();
+
+        jobs.sort_by_key(|stored| std::cmp::Reverse(stored.post.posted_timestamp));
+
+        jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1693880353;
+    const HOUR: i64 = 60 * 60;
+
+    fn job(id: &str, timestamp: i64) -> JobPost {
+        JobPost {
+            title: format!("Title {}", id),
+            link: format!("https://www.upwork.com/jobs/Job_%7E{}", id),
+            category: "web_design".to_string(),
+            posted_timestamp: timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_reports_new_ids() {
+        let mut store = JobStore::default();
+
+        let new_ids = store.insert(vec![job("01", NOW), job("02", NOW)], NOW);
+        assert_eq!(new_ids, vec!["01".to_string(), "02".to_string()]);
+
+        let new_ids = store.insert(vec![job("02", NOW), job("03", NOW)], NOW + 60);
+        assert_eq!(new_ids, vec!["03".to_string()]);
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get("02").unwrap().first_seen, NOW);
+    }
+
+    #[test]
+    fn test_prune_by_retention() {
+        let mut store = JobStore::default();
+        store.insert(
+            vec![job("old", NOW - 10 * HOUR), job("new", NOW - HOUR)],
+            NOW,
+        );
+
+        let retention = Retention {
+            max_age: Duration::from_secs(5 * HOUR as u64),
+        };
+        let pruned = store.prune(&retention, NOW);
+
+        assert_eq!(pruned, 1);
+        assert!(store.get("old").is_none());
+        assert!(store.get("new").is_some());
+    }
+
+    #[test]
+    fn test_insert_retained_skips_pruned_jobs() {
+        let mut store = JobStore::default();
+        let retention = Retention {
+            max_age: Duration::from_secs(5 * HOUR as u64),
+        };
+        store.insert(vec![job("starred", NOW - 10 * HOUR)], NOW);
+        store.toggle_starred("starred");
+
+        for _ in 0..2 {
+            let new_ids = store.insert_retained(
+                vec![
+                    job("old", NOW - 10 * HOUR),
+                    job("starred", NOW - 10 * HOUR),
+                    job("new", NOW - HOUR),
+                ],
+                &retention,
+                NOW,
+            );
+            store.prune(&retention, NOW);
+
+            assert!(new_ids.iter().all(|id| id == "new"));
+        }
+        assert!(store.get("old").is_none());
+        assert!(store.get("starred").is_some());
+        assert!(store.get("new").is_some());
+    }
+
+    #[test]
+    fn test_visible_hides_expired() {
+        let mut store = JobStore::default();
+        store.insert(
+            vec![job("stale", NOW - 72 * HOUR), job("fresh", NOW - HOUR)],
+            NOW,
+        );
+        let rules = FilterRules::default();
+
+        let visible = store.visible(View::All, &rules, false, NOW);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].post.id(), "fresh");
+
+        let visible = store.visible(View::All, &rules, true, NOW);
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[1].post.id(), "stale");
+    }
+
+    #[test]
+    fn test_views_and_annotations() {
+        let mut store = JobStore::default();
+        store.insert(
+            vec![
+                job("spam", NOW),
+                job("good", NOW - 72 * HOUR),
+                job("plain", NOW),
+            ],
+            NOW,
+        );
+        let rules = FilterRules::default();
+        let ids = |jobs: Vec<&StoredJob>| -> Vec<String> {
+            jobs.iter()
+                .map(|stored| stored.post.id().to_owned())
+                .collect()
+        };
+
+        assert_eq!(store.toggle_hidden("spam"), Some(true));
+        assert_eq!(store.toggle_starred("good"), Some(true));
+        assert!(store.set_note("good", "  applied, $45/h quoted "));
+        assert_eq!(store.toggle_starred("missing"), None);
+
+        assert_eq!(
+            ids(store.visible(View::All, &rules, false, NOW)),
+            vec!["plain"]
+        );
+        assert_eq!(
+            ids(store.visible(View::Starred, &rules, false, NOW)),
+            vec!["good"]
+        );
+        assert_eq!(
+            ids(store.visible(View::Hidden, &rules, false, NOW)),
+            vec!["spam"]
+        );
+        assert_eq!(
+            ids(store.visible(View::Notes, &rules, false, NOW)),
+            vec!["good"]
+        );
+        assert_eq!(
+            store.get("good").unwrap().note.as_deref(),
+            Some("applied, $45/h quoted")
+        );
+
+        assert!(store.set_note("good", " "));
+        assert!(store.visible(View::Notes, &rules, false, NOW).is_empty());
+    }
+
+    #[test]
+    fn test_prune_keeps_annotated_jobs() {
+        let mut store = JobStore::default();
+        store.insert(
+            vec![job("old", NOW - 10 * HOUR), job("starred", NOW - 10 * HOUR)],
+            NOW,
+        );
+        store.toggle_starred("starred");
+
+        let retention = Retention {
+            max_age: Duration::from_secs(5 * HOUR as u64),
+        };
+
+        assert_eq!(store.prune(&retention, NOW), 1);
+        assert!(store.get("starred").is_some());
+    }
+
+    #[test]
+    fn test_board_columns() {
+        let mut store = JobStore::default();
+        store.insert(vec![job("01", NOW), job("02", NOW), job("03", NOW)], NOW);
+        store.toggle_starred("01");
+        assert!(store.set_stage("02", Stage::Applied, NOW + 60));
+        assert!(store.set_stage("03", Stage::Applied, NOW + 120));
+        assert!(!store.set_stage("03", Stage::Applied, NOW + 180));
+
+        let column = |stage| -> Vec<&str> {
+            store
+                .in_stage(stage)
+                .iter()
+                .map(|stored| stored.post.id())
+                .collect()
+        };
+
+        assert_eq!(column(Stage::New), vec!["01"]);
+        assert_eq!(column(Stage::Applied), vec!["03", "02"]);
+        assert!(column(Stage::Won).is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() -> eyre::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("upwork-tui-store-{}.json", std::process::id()));
+        let mut store = JobStore::default();
+        store.insert(vec![job("01", NOW)], NOW);
+        store.set_note("01", "quoted $45/h");
+
+        store.save(&path)?;
+        let loaded = JobStore::load(&path)?;
+        fs::remove_file(&path)?;
+
+        assert_eq!(loaded.get("01"), store.get("01"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{core_opt::price_label, JobPost};
+
+pub fn fields(job: &JobPost) -> Vec<(&'static str, String)> {
+    let detail = |key: &str| job.detail.get(key).cloned().unwrap_or_default();
+    let budget = job
+        .detail
+        .get("Budget")
+        .or_else(|| job.detail.get("Hourly Range"))
+        .cloned()
+        .unwrap_or_default();
+
+    vec![
+        ("id", job.id().to_owned()),
+        ("title", job.title.clone()),
+        ("link", job.link.clone()),
+        ("category", detail("Category")),
+        ("skills", detail("Skills")),
+        ("budget", budget),
+        ("price", price_label(job)),
+        ("country", job.country.clone().unwrap_or_default()),
+        ("posted_on", job.posted_on.clone()),
+        ("description", detail("details")),
+    ]
+}
+
+/// Substitutes `{{name}}` placeholders in one pass over the template, so a
+/// value that itself contains a placeholder, e.g. a feed title, is never
+/// expanded. Unknown placeholders are left as they are.
+pub fn render<F>(template: &str, job: &JobPost, escape: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let fields = fields(job);
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        let field = after.find("}}").and_then(|end| {
+            let (_, value) = fields.iter().find(|(name, _)| *name == &after[..end])?;
+            Some((value, end))
+        });
+
+        match field {
+            Some((value, end)) => {
+                rendered.push_str(&escape(value));
+                rest = &after[end + 2..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn job() -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Hourly Range".to_string(), "$10.00-$20.00".to_string());
+        detail.insert("Skills".to_string(), "Rust, Tokio".to_string());
+
+        JobPost {
+            title: "Build a \"fast\" crawler".to_string(),
+            link: "https://www.upwork.com/jobs/Crawler_%7E0118c283ded19e9ec0".to_string(),
+            category: "web_development".to_string(),
+            detail,
+            country: Some("Singapore".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let rendered = render(
+            "{{title}} ({{budget}}) needs {{skills}} from {{country}} {{unknown}}",
+            &job(),
+            str::to_owned,
+        );
+
+        assert_eq!(
+            rendered,
+            "Build a \"fast\" crawler ($10.00-$20.00) needs Rust, Tokio from Singapore {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_render_never_expands_values() {
+        let mut job = job();
+        job.title = "{{link}} {{description}}".to_string();
+        job.detail
+            .insert("details".to_string(), "{{title}}".to_string());
+
+        let rendered = render("{{title}}|{{description}}|{{", &job, str::to_owned);
+
+        assert_eq!(rendered, "{{link}} {{description}}|{{title}}|{{");
+    }
+
+    #[test]
+    fn test_render_with_escape() {
+        let rendered = render("{{title}}", &job(), |value| value.replace('"', "\\\""));
+
+        assert_eq!(rendered, "Build a \\\"fast\\\" crawler");
+    }
+}
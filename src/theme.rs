@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::{self, bail};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub list: Style,
+    pub selection: Style,
+    pub unread: Style,
+    pub starred: Style,
+    pub expired: Style,
+    pub price: Style,
+    pub restricted: Style,
+    pub gauge: Style,
+    pub missing: Style,
+    pub border: Style,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Slot {
+    List,
+    Selection,
+    Unread,
+    Starred,
+    Expired,
+    Price,
+    Restricted,
+    Gauge,
+    Missing,
+    Border,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorSupport {
+    #[default]
+    Auto,
+    Truecolor,
+    Ansi16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleDef {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub add_modifier: Modifier,
+    pub sub_modifier: Modifier,
+}
+
+impl From<StyleDef> for Style {
+    fn from(def: StyleDef) -> Self {
+        Style {
+            fg: def.fg,
+            bg: def.bg,
+            add_modifier: def.add_modifier,
+            sub_modifier: def.sub_modifier,
+            ..Style::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: Option<String>,
+    pub colors: ColorSupport,
+    pub styles: BTreeMap<Slot, StyleDef>,
+}
+
+impl Theme {
+    pub fn from_config(config: &ThemeConfig) -> eyre::Result<Self> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let truecolor = match config.colors {
+            ColorSupport::Auto => std::env::var("COLORTERM")
+                .is_ok_and(|value| value == "truecolor" || value == "24bit"),
+            ColorSupport::Truecolor => true,
+            ColorSupport::Ansi16 => false,
+        };
+
+        Self::resolve(config, no_color, truecolor)
+    }
+
+    pub fn resolve(config: &ThemeConfig, no_color: bool, truecolor: bool) -> eyre::Result<Self> {
+        let mut theme = match &config.name {
+            Some(name) => Self::builtin(name)?,
+            None => Self::default(),
+        };
+
+        for (slot, def) in &config.styles {
+            let style = theme.slot_mut(*slot);
+            *style = style.patch(Style::from(*def));
+        }
+
+        if no_color {
+            theme.map_styles(strip_colors);
+            if theme.selection.add_modifier.is_empty() {
+                theme.selection = theme.selection.add_modifier(Modifier::REVERSED);
+            }
+        } else if !truecolor {
+            theme.map_styles(to_ansi16);
+        }
+
+        Ok(theme)
+    }
+
+    pub fn builtin(name: &str) -> eyre::Result<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "gruvbox" => Self {
+                list: Style::new().fg(Color::Rgb(0xeb, 0xdb, 0xb2)),
+                selection: Style::new()
+                    .fg(Color::Rgb(0x28, 0x28, 0x28))
+                    .bg(Color::Rgb(0xfa, 0xbd, 0x2f)),
+                unread: Style::new().add_modifier(Modifier::BOLD),
+                starred: Style::new().fg(Color::Rgb(0xfe, 0x80, 0x19)),
+                expired: Style::new().fg(Color::Rgb(0x92, 0x83, 0x74)),
+                price: Style::new().fg(Color::Rgb(0xb8, 0xbb, 0x26)),
+                restricted: Style::new().fg(Color::Rgb(0xfb, 0x49, 0x34)),
+                gauge: Style::new().fg(Color::Rgb(0x83, 0xa5, 0x98)),
+                missing: Style::new().fg(Color::Rgb(0xfa, 0xbd, 0x2f)),
+                border: Style::new().fg(Color::Rgb(0x66, 0x5c, 0x54)),
+            },
+            "solarized" => Self {
+                list: Style::new().fg(Color::Rgb(0x83, 0x94, 0x96)),
+                selection: Style::new()
+                    .fg(Color::Rgb(0xfd, 0xf6, 0xe3))
+                    .bg(Color::Rgb(0x26, 0x8b, 0xd2)),
+                unread: Style::new()
+                    .fg(Color::Rgb(0x93, 0xa1, 0xa1))
+                    .add_modifier(Modifier::BOLD),
+                starred: Style::new().fg(Color::Rgb(0xb5, 0x89, 0x00)),
+                expired: Style::new().fg(Color::Rgb(0x58, 0x6e, 0x75)),
+                price: Style::new().fg(Color::Rgb(0x85, 0x99, 0x00)),
+                restricted: Style::new().fg(Color::Rgb(0xdc, 0x32, 0x2f)),
+                gauge: Style::new().fg(Color::Rgb(0x2a, 0xa1, 0x98)),
+                missing: Style::new().fg(Color::Rgb(0xcb, 0x4b, 0x16)),
+                border: Style::new().fg(Color::Rgb(0x07, 0x36, 0x42)),
+            },
+            "mono" => Self {
+                list: Style::new(),
+                selection: Style::new().add_modifier(Modifier::REVERSED),
+                unread: Style::new().add_modifier(Modifier::BOLD),
+                starred: Style::new().add_modifier(Modifier::UNDERLINED),
+                expired: Style::new().add_modifier(Modifier::DIM),
+                price: Style::new(),
+                restricted: Style::new().add_modifier(Modifier::ITALIC),
+                gauge: Style::new(),
+                missing: Style::new().add_modifier(Modifier::CROSSED_OUT),
+                border: Style::new(),
+            },
+            _ => bail!(
+                "unknown theme `{}`, expected one of default, gruvbox, solarized, mono",
+                name
+            ),
+        };
+
+        Ok(theme)
+    }
+
+    fn slot_mut(&mut self, slot: Slot) -> &mut Style {
+        match slot {
+            Slot::List => &mut self.list,
+            Slot::Selection => &mut self.selection,
+            Slot::Unread => &mut self.unread,
+            Slot::Starred => &mut self.starred,
+            Slot::Expired => &mut self.expired,
+            Slot::Price => &mut self.price,
+            Slot::Restricted => &mut self.restricted,
+            Slot::Gauge => &mut self.gauge,
+            Slot::Missing => &mut self.missing,
+            Slot::Border => &mut self.border,
+        }
+    }
+
+    fn map_styles(&mut self, f: fn(Style) -> Style) {
+        for style in [
+            &mut self.list,
+            &mut self.selection,
+            &mut self.unread,
+            &mut self.starred,
+            &mut self.expired,
+            &mut self.price,
+            &mut self.restricted,
+            &mut self.gauge,
+            &mut self.missing,
+            &mut self.border,
+        ] {
+            *style = f(*style);
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            list: Style::new(),
+            selection: Style::new().add_modifier(Modifier::REVERSED),
+            unread: Style::new().add_modifier(Modifier::BOLD),
+            starred: Style::new().fg(Color::Yellow),
+            expired: Style::new().add_modifier(Modifier::DIM),
+            price: Style::new().fg(Color::Green),
+            restricted: Style::new().fg(Color::Red),
+            gauge: Style::new().fg(Color::Cyan),
+            missing: Style::new().fg(Color::Magenta),
+            border: Style::new(),
+        }
+    }
+}
+
+fn strip_colors(style: Style) -> Style {
+    Style {
+        fg: None,
+        bg: None,
+        ..style
+    }
+}
+
+fn to_ansi16(style: Style) -> Style {
+    Style {
+        fg: style.fg.map(nearest_ansi16),
+        bg: style.bg.map(nearest_ansi16),
+        ..style
+    }
+}
+
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn nearest_ansi16(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) if index < 16 => return ANSI16[index as usize].0,
+        Color::Indexed(index) if index < 232 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        Color::Indexed(index) => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+        named => return named,
+    };
+
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, cr) + d(g, cg) + d(b, cb)
+    };
+
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(named, _)| *named)
+        .unwrap_or(Color::Reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> ThemeConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_with_overrides() -> eyre::Result<()> {
+        let config = config(
+            r##"
+            name = "gruvbox"
+            colors = "truecolor"
+
+            [styles]
+            price = { fg = "#00ff00", add_modifier = "BOLD | ITALIC" }
+            border = { fg = "blue" }
+            "##,
+        );
+        let theme = Theme::resolve(&config, false, true)?;
+
+        assert_eq!(
+            theme.price,
+            Style::new()
+                .fg(Color::Rgb(0, 255, 0))
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(theme.border, Style::new().fg(Color::Blue));
+        assert_eq!(theme.list, Theme::builtin("gruvbox")?.list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ansi16_fallback() -> eyre::Result<()> {
+        let theme = Theme::resolve(&config("name = \"gruvbox\""), false, false)?;
+
+        assert_eq!(theme.selection.bg, Some(Color::Yellow));
+        assert_eq!(theme.price.fg, Some(Color::Yellow));
+        assert_eq!(nearest_ansi16(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(nearest_ansi16(Color::Indexed(4)), Color::Blue);
+        assert_eq!(nearest_ansi16(Color::Indexed(244)), Color::DarkGray);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_color_keeps_modifiers() -> eyre::Result<()> {
+        let theme = Theme::resolve(&config("name = \"gruvbox\""), true, true)?;
+
+        assert_eq!(theme.price, Style::new());
+        assert_eq!(theme.unread, Style::new().add_modifier(Modifier::BOLD));
+        assert_eq!(
+            theme.selection,
+            Style::new().add_modifier(Modifier::REVERSED)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_theme() {
+        assert!(Theme::resolve(&config("name = \"neon\""), false, true).is_err());
+        assert!(toml::from_str::<ThemeConfig>("[styles]\nlist = { fg = \"nope\" }").is_err());
+        assert!(toml::from_str::<ThemeConfig>("[styles]\nheader = { fg = \"red\" }").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    io::{stderr, Stderr},
+    panic::{self, PanicHookInfo},
+    process,
+    time::Duration,
+};
+
+use color_eyre::{
+    config::HookBuilder,
+    eyre::{self, eyre},
+};
+use crossterm::{
+    cursor,
+    event::{Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use futures::StreamExt;
+use ratatui::{backend::CrosstermBackend, Terminal};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Tick,
+    Key(KeyEvent),
+    Resize(u16, u16),
+    Error(String),
+}
+
+pub struct Tui {
+    pub terminal: Terminal<CrosstermBackend<Stderr>>,
+    task: Option<JoinHandle<()>>,
+    event_rx: mpsc::UnboundedReceiver<Event>,
+    event_tx: mpsc::UnboundedSender<Event>,
+    tick_rate: Duration,
+}
+
+impl Tui {
+    pub fn new(tick_rate: Duration) -> eyre::Result<Self> {
+        let terminal = Terminal::new(CrosstermBackend::new(stderr()))?;
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        Ok(Self {
+            terminal,
+            task: None,
+            event_rx,
+            event_tx,
+            tick_rate,
+        })
+    }
+
+    pub fn enter(&mut self) -> eyre::Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(stderr(), EnterAlternateScreen, cursor::Hide)?;
+        self.terminal.clear()?;
+        self.start();
+
+        Ok(())
+    }
+
+    pub fn exit(&mut self) -> eyre::Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+
+        Self::restore()
+    }
+
+    pub fn restore() -> eyre::Result<()> {
+        if terminal::is_raw_mode_enabled()? {
+            execute!(stderr(), LeaveAlternateScreen, cursor::Show)?;
+            terminal::disable_raw_mode()?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn next(&mut self) -> eyre::Result<Event> {
+        self.event_rx
+            .recv()
+            .await
+            .ok_or_else(|| eyre!("terminal event stream closed"))
+    }
+
+    fn start(&mut self) {
+        let event_tx = self.event_tx.clone();
+        let tick_rate = self.tick_rate;
+
+        let task = tokio::spawn(async move {
+            let mut reader = EventStream::new();
+            let mut tick = tokio::time::interval(tick_rate);
+
+            loop {
+                let event = tokio::select! {
+                    maybe_event = reader.next() => match maybe_event {
+                        Some(Ok(CrosstermEvent::Key(key))) if key.kind == KeyEventKind::Press => {
+                            Event::Key(key)
+                        }
+                        Some(Ok(CrosstermEvent::Resize(x, y))) => Event::Resize(x, y),
+                        Some(Ok(_)) => continue,
+                        Some(Err(err)) => Event::Error(err.to_string()),
+                        None => break,
+                    },
+                    _ = tick.tick() => Event::Tick,
+                };
+
+                if event_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.task = Some(task);
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = self.exit();
+    }
+}
+
+type PanicHandler = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync>;
+
+/// Installs color-eyre for errors and a panic hook that leaves the alternate
+/// screen and raw mode before printing. Fatal errors returned from `App::run`
+/// restore the terminal the same way, as `Tui` is dropped on the way out.
+pub fn install_hooks() -> eyre::Result<()> {
+    let (_, eyre_hook) = HookBuilder::default().into_hooks();
+    eyre_hook.install()?;
+
+    let handler = panic_handler();
+    panic::set_hook(Box::new(move |info| {
+        let _ = Tui::restore();
+        handler(info);
+        // A panic in a spawned task would otherwise leave the app running
+        // on a terminal that is no longer set up for it, and a frame drawn
+        // meanwhile may have hidden the cursor again.
+        let _ = execute!(stderr(), cursor::Show);
+        process::exit(101);
+    }));
+
+    Ok(())
+}
+
+/// Writes a crash report to a temporary file and asks the user to send it in.
+#[cfg(not(debug_assertions))]
+fn panic_handler() -> PanicHandler {
+    let metadata = human_panic::metadata!();
+
+    Box::new(move |info| {
+        let report = human_panic::handle_dump(&metadata, info);
+        let _ = human_panic::print_msg(report, &metadata);
+    })
+}
+
+/// Prints the panic with a readable backtrace, most recent call last.
+#[cfg(debug_assertions)]
+fn panic_handler() -> PanicHandler {
+    better_panic::Settings::auto()
+        .most_recent_first(false)
+        .lineno_suffix(true)
+        .create_panic_handler()
+}
@@ -0,0 +1,403 @@
+use std::{collections::HashMap, time::Duration};
+
+use color_eyre::eyre::{self, bail, eyre, WrapErr};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Client, StatusCode,
+};
+use serde::Deserialize;
+
+use crate::{template, JobPost};
+
+const MAX_RETRIES: u32 = 10;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub template: Option<String>,
+    /// At most `MAX_RETRIES`
+    pub retries: u32,
+    /// Doubled after every failed attempt, up to `MAX_BACKOFF`
+    #[serde(with = "humantime_serde")]
+    pub backoff: Duration,
+    /// For one attempt, so a hanging endpoint cannot hold up later jobs
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            headers: HashMap::new(),
+            template: None,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            ..Default::default()
+        }
+    }
+}
+
+pub struct Webhook {
+    config: WebhookConfig,
+    headers: HeaderMap,
+    client: Client,
+}
+
+impl Webhook {
+    pub fn new(config: WebhookConfig) -> eyre::Result<Self> {
+        let url = reqwest::Url::parse(&config.url)
+            .wrap_err_with(|| format!("invalid webhook url `{}`", config.url))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("webhook url `{}` must use http or https", config.url);
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        for (name, value) in &config.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .wrap_err_with(|| format!("invalid webhook header name `{}`", name))?;
+            let value = HeaderValue::from_str(value)
+                .wrap_err_with(|| format!("invalid value for webhook header `{}`", name))?;
+            headers.insert(name, value);
+        }
+
+        if config.retries > MAX_RETRIES {
+            bail!("webhook retries must be at most {}", MAX_RETRIES);
+        }
+
+        if config.timeout.is_zero() {
+            bail!("webhook timeout must be longer than 0s");
+        }
+
+        let client = Client::builder().timeout(config.timeout).build()?;
+        let webhook = Self {
+            config,
+            headers,
+            client,
+        };
+
+        // Catch a broken template now rather than on the first new job.
+        webhook.payload(&sample_job())?;
+
+        Ok(webhook)
+    }
+
+    pub fn payload(&self, job: &JobPost) -> eyre::Result<String> {
+        match &self.config.template {
+            Some(template) => {
+                let payload = template::render(template, job, escape_json);
+                serde_json::from_str::<serde_json::Value>(&payload)
+                    .wrap_err("webhook template did not render to valid JSON")?;
+
+                Ok(payload)
+            }
+            None => Ok(serde_json::to_string(job)?),
+        }
+    }
+
+    pub async fn send(&self, job: &JobPost) -> eyre::Result<()> {
+        let payload = self.payload(job)?;
+        let mut attempt = 0;
+
+        loop {
+            let result = self
+                .client
+                .post(&self.config.url)
+                .headers(self.headers.clone())
+                .body(payload.clone())
+                .send()
+                .await;
+
+            let retryable = match result {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if !is_retryable(status) {
+                        bail!("webhook rejected job {}: {}", job.id(), status);
+                    }
+                    eyre!("webhook responded with {}", status)
+                }
+                Err(err) => eyre!(err),
+            };
+
+            if attempt >= self.config.retries {
+                return Err(retryable.wrap_err(format!(
+                    "webhook failed for job {} after {} attempts",
+                    job.id(),
+                    attempt + 1
+                )));
+            }
+
+            tokio::time::sleep(self.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        2u32.checked_pow(attempt)
+            .and_then(|factor| self.config.backoff.checked_mul(factor))
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+
+    pub async fn send_all(&self, jobs: &[JobPost]) -> Vec<eyre::Report> {
+        let mut errors = Vec::new();
+
+        for job in jobs {
+            if let Err(err) = self.send(job).await {
+                errors.push(err);
+            }
+        }
+
+        errors
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Stands in for a real job when checking that the template renders.
+fn sample_job() -> JobPost {
+    JobPost {
+        title: "Sample \"job\"".to_string(),
+        link: "https://www.upwork.com/jobs/Sample_%7E01".to_string(),
+        category: "sample".to_string(),
+        detail: HashMap::from([
+            ("details".to_string(), "Line one\nline two".to_string()),
+            ("Budget".to_string(), "$80".to_string()),
+        ]),
+        posted_on: "Mon, 29 Apr 2024 09:00:00 +0000".to_string(),
+        posted_timestamp: 1714381200,
+        country: Some("United States".to_string()),
+        ..Default::default()
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_owned()).to_string();
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    #[derive(Debug, Clone)]
+    struct Recorded {
+        headers: Vec<String>,
+        body: String,
+    }
+
+    async fn stand_in_server(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<Recorded>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let requests = recorded.clone();
+
+        tokio::spawn(async move {
+            for status in statuses {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut headers = Vec::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    let line = line.trim_end().to_owned();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    headers.push(line);
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).await.unwrap();
+                requests.lock().unwrap().push(Recorded {
+                    headers,
+                    body: String::from_utf8(body).unwrap(),
+                });
+
+                let response = format!(
+                    "HTTP/1.1 {} Stand-in\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                reader
+                    .into_inner()
+                    .write_all(response.as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
+
+        (url, recorded)
+    }
+
+    fn job() -> JobPost {
+        let mut detail = HashMap::new();
+        detail.insert("Budget".to_string(), "$80".to_string());
+
+        JobPost {
+            title: "Logo \"refresh\"".to_string(),
+            link: "https://www.upwork.com/jobs/Logo_%7E014431774d3a21a1a4".to_string(),
+            category: "graphic_design".to_string(),
+            detail,
+            posted_on: "Sun, 03 Sep 2023 04:19:13 +0000".to_string(),
+            posted_timestamp: 1693707553,
+            ..Default::default()
+        }
+    }
+
+    fn config(url: String) -> WebhookConfig {
+        WebhookConfig {
+            backoff: Duration::from_millis(1),
+            ..WebhookConfig::new(url)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_default_payload_with_headers() -> eyre::Result<()> {
+        let (url, recorded) = stand_in_server(vec![200]).await;
+        let mut config = config(url);
+        config
+            .headers
+            .insert("X-Api-Key".to_string(), "secret".to_string());
+
+        Webhook::new(config)?.send(&job()).await?;
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded[0]
+            .headers
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("x-api-key: secret")));
+
+        let sent: JobPost = serde_json::from_str(&recorded[0].body)?;
+        assert_eq!(sent, job());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_templated_payload() -> eyre::Result<()> {
+        let (url, recorded) = stand_in_server(vec![200]).await;
+        let mut config = config(url);
+        config.template = Some(r#"{"text": "{{title}} ({{budget}}) {{link}}"}"#.to_string());
+
+        Webhook::new(config)?.send(&job()).await?;
+
+        let body: serde_json::Value = serde_json::from_str(&recorded.lock().unwrap()[0].body)?;
+        assert_eq!(
+            body["text"],
+            "Logo \"refresh\" ($80) https://www.upwork.com/jobs/Logo_%7E014431774d3a21a1a4"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_server_errors() -> eyre::Result<()> {
+        let (url, recorded) = stand_in_server(vec![500, 429, 200]).await;
+
+        Webhook::new(config(url))?.send(&job()).await?;
+
+        assert_eq!(recorded.lock().unwrap().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up() -> eyre::Result<()> {
+        let (url, recorded) = stand_in_server(vec![500, 500]).await;
+        let mut config = config(url);
+        config.retries = 1;
+
+        let res = Webhook::new(config)?.send(&job()).await;
+
+        assert!(res.is_err());
+        assert_eq!(recorded.lock().unwrap().len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_client_errors() -> eyre::Result<()> {
+        let (url, recorded) = stand_in_server(vec![400]).await;
+
+        let res = Webhook::new(config(url))?.send(&job()).await;
+
+        assert!(res.is_err());
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_times_out() -> eyre::Result<()> {
+        // Accepts the connection but never answers.
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/hook", listener.local_addr()?);
+        tokio::spawn(async move {
+            let _stream = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        let mut config = config(url);
+        config.retries = 0;
+        config.timeout = Duration::from_millis(50);
+
+        let res = tokio::time::timeout(Duration::from_secs(5), Webhook::new(config)?.send(&job()))
+            .await?;
+
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backoff_is_capped() -> eyre::Result<()> {
+        let webhook = Webhook::new(WebhookConfig {
+            retries: MAX_RETRIES,
+            ..WebhookConfig::new("http://127.0.0.1/hook".to_string())
+        })?;
+
+        assert_eq!(webhook.backoff(0), Duration::from_secs(1));
+        assert_eq!(webhook.backoff(3), Duration::from_secs(8));
+        assert_eq!(webhook.backoff(10), MAX_BACKOFF);
+        assert_eq!(webhook.backoff(40), MAX_BACKOFF);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let mut config = WebhookConfig::new("http://127.0.0.1/hook".to_string());
+        config.template = Some("{{title}}".to_string());
+        assert!(Webhook::new(config).is_err());
+
+        let mut config = WebhookConfig::new("http://127.0.0.1/hook".to_string());
+        config.retries = 40;
+        assert!(Webhook::new(config).is_err());
+    }
+}
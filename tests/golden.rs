@@ -0,0 +1,29 @@
+//! Parses each feed in `tests/fixtures` and compares the resulting `JobPost`s
+//! with the snapshots in `tests/snapshots`. After an intended parser change,
+//! review and accept the new output with `cargo insta review`.
+//!
+//! The fixtures are hand-written in the layout of Upwork's RSS feeds, not
+//! captured ones, so their titles, links and job ids are made up.
+
+use std::fs;
+
+use upwork_tui::core_opt::parse_xml::parse_xml;
+
+#[test]
+fn test_fixture_feeds() {
+    let mut settings = insta::Settings::clone_current();
+    // `JobPost::detail` is a HashMap, so sort it for stable snapshots.
+    settings.set_sort_maps(true);
+    settings.set_prepend_module_to_snapshot(false);
+
+    settings.bind(|| {
+        insta::glob!("fixtures/*.xml", |path| {
+            let feed = fs::read(path).unwrap();
+
+            match parse_xml(&feed[..]) {
+                Ok(jobs) => insta::assert_json_snapshot!(jobs),
+                Err(err) => insta::assert_snapshot!(format!("{:#}", err)),
+            }
+        });
+    });
+}
@@ -0,0 +1,185 @@
+//! Property tests feeding random HTML fragments and RSS documents into
+//! `get_detail` and `parse_xml`: neither may panic, and well-formed Upwork
+//! items must come back out exactly as they were written.
+
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use proptest::prelude::*;
+use upwork_tui::core_opt::parse_xml::{get_detail, parse_xml};
+
+/// Pieces Upwork descriptions are made of, plus a few broken ones.
+fn html_fragment() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        "[^<&]{0,20}",
+        Just("<b>".to_string()),
+        Just("</b>".to_string()),
+        Just("<br />".to_string()),
+        Just("<a href=\"https://www.upwork.com\">".to_string()),
+        Just("</a>".to_string()),
+        Just("<!-- note -->".to_string()),
+        Just("<!--".to_string()),
+        Just("<".to_string()),
+        Just(">".to_string()),
+        "&[a-zA-Z#0-9]{0,8};?",
+        "<b>[A-Za-z ]{0,12}</b>:[^<]{0,20}",
+    ];
+
+    prop::collection::vec(piece, 0..24).prop_map(|pieces| pieces.concat())
+}
+
+fn label() -> impl Strategy<Value = String> {
+    "[A-Z][a-z]{1,8}( [A-Z][a-z]{1,8})?"
+        .prop_filter("Posted On is skipped", |label| label != "Posted On")
+}
+
+/// Text without markup, as it appears between the tags of a description.
+fn plain_text() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9][A-Za-z0-9 .,!?$'()-]{0,40}"
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    title: String,
+    id: String,
+    details: String,
+    category: String,
+    fields: BTreeMap<String, String>,
+    timestamp: i64,
+}
+
+fn item() -> impl Strategy<Value = Item> {
+    (
+        "\\PC{0,40}",
+        "[0-9a-f]{18}",
+        plain_text(),
+        "[A-Z][a-z]{2,10}( [A-Z][a-z]{2,10})?",
+        prop::collection::btree_map(label(), plain_text(), 0..5),
+        0i64..4_000_000_000,
+    )
+        .prop_map(|(title, id, details, category, mut fields, timestamp)| {
+            fields.remove("Category");
+            Item {
+                title,
+                id,
+                details,
+                category,
+                fields,
+                timestamp,
+            }
+        })
+}
+
+fn description(item: &Item) -> String {
+    let mut html = format!("{}<br /><br />", item.details);
+    for (label, value) in &item.fields {
+        html.push_str(&format!("<b>{}</b>: {}\n<br />", label, value));
+    }
+    html.push_str(&format!(
+        "<b>Posted On</b>: April 29, 2024 09:07 UTC<br /><b>Category</b>: {}<br />",
+        item.category
+    ));
+
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn feed(items: &[Item]) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0"><channel><title>All jobs</title>"#,
+    );
+
+    for item in items {
+        let posted = DateTime::from_timestamp(item.timestamp, 0)
+            .unwrap()
+            .format("%a, %d %b %Y %H:%M:%S +0000");
+        xml.push_str(&format!(
+            "<item><title>{}</title><link>https://www.upwork.com/jobs/Job_%7E{}?source=rss</link><description>{}</description><pubDate>{}</pubDate></item>",
+            escape(&item.title),
+            item.id,
+            escape(&description(item)),
+            posted
+        ));
+    }
+
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+proptest! {
+    #[test]
+    fn test_get_detail_never_panics(text in "\\PC*") {
+        let _ = get_detail(&text);
+    }
+
+    #[test]
+    fn test_get_detail_never_panics_on_fragments(html in html_fragment()) {
+        let _ = get_detail(&html);
+    }
+
+    #[test]
+    fn test_get_detail_reads_back_labels(item in item()) {
+        let detail = get_detail(&description(&item)).unwrap();
+
+        prop_assert_eq!(&detail["details"], &item.details);
+        prop_assert_eq!(&detail["Category"], &item.category);
+        prop_assert!(!detail.contains_key("Posted On"));
+        for (label, value) in &item.fields {
+            let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            prop_assert_eq!(&detail[label], &value);
+        }
+        prop_assert_eq!(detail.len(), item.fields.len() + 2);
+    }
+
+    #[test]
+    fn test_parse_xml_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        let _ = parse_xml(&bytes[..]);
+    }
+
+    #[test]
+    fn test_parse_xml_never_panics_on_items(
+        items in prop::collection::vec(
+            (
+                prop::option::of("\\PC{0,20}"),
+                prop::option::of("[a-z:/.?=~%0-9]{0,30}"),
+                prop::option::of(html_fragment()),
+                prop::option::of("\\PC{0,31}"),
+            ),
+            0..4,
+        )
+    ) {
+        let mut xml = String::from("<rss><channel>");
+        for (title, link, description, date) in &items {
+            xml.push_str("<item>");
+            for (tag, text) in [("title", title), ("link", link), ("description", description), ("pubDate", date)] {
+                if let Some(text) = text {
+                    xml.push_str(&format!("<{0}>{1}</{0}>", tag, escape(text)));
+                }
+            }
+            xml.push_str("</item>");
+        }
+        xml.push_str("</channel></rss>");
+
+        let _ = parse_xml(xml.as_bytes());
+    }
+
+    #[test]
+    fn test_parse_xml_round_trips(items in prop::collection::vec(item(), 0..4)) {
+        let jobs = parse_xml(feed(&items).as_bytes()).unwrap();
+
+        prop_assert_eq!(jobs.len(), items.len());
+        for (job, item) in jobs.iter().zip(&items) {
+            prop_assert_eq!(&job.title, &item.title);
+            prop_assert_eq!(job.id(), item.id.as_str());
+            prop_assert_eq!(job.posted_timestamp, item.timestamp);
+            prop_assert_eq!(&job.category, &item.category.to_lowercase().replace(' ', "_"));
+            prop_assert_eq!(&job.detail["details"], &item.details);
+            prop_assert_eq!(job.detail.len(), item.fields.len() + 2);
+        }
+    }
+}